@@ -0,0 +1,305 @@
+use num_bigint::{BigInt, BigUint};
+use ton_block::Deserializable;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsValue;
+
+use crate::tokens_object::*;
+use crate::utils::*;
+
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct CellBuilder {
+    #[wasm_bindgen(skip)]
+    pub inner: ton_types::BuilderData,
+}
+
+#[wasm_bindgen]
+impl CellBuilder {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> CellBuilder {
+        Self::default()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn bits(&self) -> u32 {
+        self.inner.bits_used() as u32
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn refs(&self) -> u32 {
+        self.inner.references_used() as u32
+    }
+
+    #[wasm_bindgen(js_name = "storeBit")]
+    pub fn store_bit(&mut self, value: bool) -> Result<(), JsValue> {
+        if value {
+            self.inner.append_bit_one().handle_error()?;
+        } else {
+            self.inner.append_bit_zero().handle_error()?;
+        }
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = "storeBits")]
+    pub fn store_bits(&mut self, data: &str, bits: usize) -> Result<(), JsValue> {
+        let data = parse_base64_or_hex_bytes(data).handle_error()?;
+        if bits > data.len() * 8 {
+            return Err(CellError::NotEnoughData).handle_error();
+        }
+        self.inner.append_raw(&data, bits).handle_error()?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = "storeBytes")]
+    pub fn store_bytes(&mut self, data: &str) -> Result<(), JsValue> {
+        let data = parse_base64_or_hex_bytes(data).handle_error()?;
+        self.inner
+            .append_raw(&data, data.len() * 8)
+            .handle_error()?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = "storeUint")]
+    pub fn store_uint(&mut self, value: NumberLike, bits: usize) -> Result<(), JsValue> {
+        self.store_token(&ton_abi::ParamType::Uint(bits), value)
+    }
+
+    #[wasm_bindgen(js_name = "storeInt")]
+    pub fn store_int(&mut self, value: NumberLike, bits: usize) -> Result<(), JsValue> {
+        self.store_token(&ton_abi::ParamType::Int(bits), value)
+    }
+
+    #[wasm_bindgen(js_name = "storeVarUint")]
+    pub fn store_var_uint(&mut self, value: NumberLike, size: usize) -> Result<(), JsValue> {
+        self.store_token(&ton_abi::ParamType::VarUint(size), value)
+    }
+
+    #[wasm_bindgen(js_name = "storeVarInt")]
+    pub fn store_var_int(&mut self, value: NumberLike, size: usize) -> Result<(), JsValue> {
+        self.store_token(&ton_abi::ParamType::VarInt(size), value)
+    }
+
+    #[wasm_bindgen(js_name = "storeCoins")]
+    pub fn store_coins(&mut self, value: NumberLike) -> Result<(), JsValue> {
+        self.store_token(&ton_abi::ParamType::Token, value)
+    }
+
+    #[wasm_bindgen(js_name = "storeAddress")]
+    pub fn store_address(&mut self, address: Option<String>) -> Result<(), JsValue> {
+        use ton_block::Serializable;
+
+        let address = match address {
+            Some(address) => match parse_address(&address)? {
+                ton_block::MsgAddressInt::AddrStd(addr) => ton_block::MsgAddress::AddrStd(addr),
+                ton_block::MsgAddressInt::AddrVar(addr) => ton_block::MsgAddress::AddrVar(addr),
+            },
+            None => ton_block::MsgAddress::AddrNone,
+        };
+        address.write_to(&mut self.inner).handle_error()
+    }
+
+    #[wasm_bindgen(js_name = "storeRef")]
+    pub fn store_ref(&mut self, boc: &Boc) -> Result<(), JsValue> {
+        let cell = parse_cell(boc)?;
+        self.inner.checked_append_reference(cell).handle_error()?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = "storeSlice")]
+    pub fn store_slice(&mut self, slice: &CellSlice) -> Result<(), JsValue> {
+        self.inner
+            .checked_append_references_and_data(&slice.inner)
+            .handle_error()?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = "storeBuilder")]
+    pub fn store_builder(&mut self, builder: &CellBuilder) -> Result<(), JsValue> {
+        self.inner.append_builder(&builder.inner).handle_error()?;
+        Ok(())
+    }
+
+    #[wasm_bindgen]
+    pub fn build(&self) -> Result<String, JsValue> {
+        let cell = self.inner.clone().into_cell().handle_error()?;
+        serialize_boc(&cell)
+    }
+
+    #[wasm_bindgen(js_name = "buildBytes")]
+    pub fn build_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        let cell = self.inner.clone().into_cell().handle_error()?;
+        serialize_boc_bytes(&cell)
+    }
+}
+
+impl CellBuilder {
+    fn store_token(
+        &mut self,
+        param: &ton_abi::ParamType,
+        value: NumberLike,
+    ) -> Result<(), JsValue> {
+        let builder = parse_token_value(param, value.into())?
+            .pack_into_chain(&ton_abi::contract::ABI_VERSION_2_2)
+            .handle_error()?;
+        self.inner.append_builder(&builder).handle_error()?;
+        Ok(())
+    }
+}
+
+#[wasm_bindgen]
+pub struct CellSlice {
+    #[wasm_bindgen(skip)]
+    pub inner: ton_types::SliceData,
+}
+
+#[wasm_bindgen]
+impl CellSlice {
+    #[wasm_bindgen(constructor)]
+    pub fn new(boc: &Boc) -> Result<CellSlice, JsValue> {
+        Ok(Self {
+            inner: parse_cell_slice(boc)?,
+        })
+    }
+
+    #[wasm_bindgen(getter, js_name = "remainingBits")]
+    pub fn remaining_bits(&self) -> u32 {
+        self.inner.remaining_bits() as u32
+    }
+
+    #[wasm_bindgen(getter, js_name = "remainingRefs")]
+    pub fn remaining_refs(&self) -> u32 {
+        self.inner.remaining_references() as u32
+    }
+
+    #[wasm_bindgen(js_name = "loadBit")]
+    pub fn load_bit(&mut self) -> Result<bool, JsValue> {
+        self.inner.get_next_bit().handle_error()
+    }
+
+    #[wasm_bindgen(js_name = "loadBits")]
+    pub fn load_bits(&mut self, bits: usize) -> Result<String, JsValue> {
+        self.inner
+            .get_next_bits(bits)
+            .map(base64::encode)
+            .handle_error()
+    }
+
+    #[wasm_bindgen(js_name = "loadBytes")]
+    pub fn load_bytes(&mut self, len: usize) -> Result<String, JsValue> {
+        self.inner
+            .get_next_bytes(len)
+            .map(base64::encode)
+            .handle_error()
+    }
+
+    #[wasm_bindgen(js_name = "loadUint")]
+    pub fn load_uint(&mut self, bits: usize) -> Result<String, JsValue> {
+        self.load_biguint(bits).map(|value| value.to_string())
+    }
+
+    #[wasm_bindgen(js_name = "loadInt")]
+    pub fn load_int(&mut self, bits: usize) -> Result<String, JsValue> {
+        let value = BigInt::from(self.load_biguint(bits)?);
+        let value = if bits > 0 && value.bit(bits as u64 - 1) {
+            value - (BigInt::from(1) << bits)
+        } else {
+            value
+        };
+        Ok(value.to_string())
+    }
+
+    #[wasm_bindgen(js_name = "loadVarUint")]
+    pub fn load_var_uint(&mut self, size: usize) -> Result<String, JsValue> {
+        let len = self.load_var_len(size)?;
+        let bytes = self.inner.get_next_bytes(len).handle_error()?;
+        Ok(BigUint::from_bytes_be(&bytes).to_string())
+    }
+
+    #[wasm_bindgen(js_name = "loadVarInt")]
+    pub fn load_var_int(&mut self, size: usize) -> Result<String, JsValue> {
+        let len = self.load_var_len(size)?;
+        let bytes = self.inner.get_next_bytes(len).handle_error()?;
+        Ok(BigInt::from_signed_bytes_be(&bytes).to_string())
+    }
+
+    #[wasm_bindgen(js_name = "loadCoins")]
+    pub fn load_coins(&mut self) -> Result<String, JsValue> {
+        let value = ton_block::Grams::construct_from(&mut self.inner).handle_error()?;
+        Ok(value.0.to_string())
+    }
+
+    #[wasm_bindgen(js_name = "loadAddress")]
+    pub fn load_address(&mut self) -> Result<Option<String>, JsValue> {
+        let address = ton_block::MsgAddress::construct_from(&mut self.inner).handle_error()?;
+        Ok(match address {
+            ton_block::MsgAddress::AddrNone => None,
+            address => Some(address.to_string()),
+        })
+    }
+
+    #[wasm_bindgen(js_name = "loadRef")]
+    pub fn load_ref(&mut self) -> Result<String, JsValue> {
+        let cell = self.inner.checked_drain_reference().handle_error()?;
+        serialize_boc(&cell)
+    }
+
+    #[wasm_bindgen(js_name = "loadRefBytes")]
+    pub fn load_ref_bytes(&mut self) -> Result<Vec<u8>, JsValue> {
+        let cell = self.inner.checked_drain_reference().handle_error()?;
+        serialize_boc_bytes(&cell)
+    }
+
+    #[wasm_bindgen]
+    pub fn skip(&mut self, bits: usize, refs: Option<usize>) -> Result<(), JsValue> {
+        self.inner.move_by(bits).handle_error()?;
+        for _ in 0..refs.unwrap_or_default() {
+            self.inner.checked_drain_reference().handle_error()?;
+        }
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = "toBoc")]
+    pub fn to_boc(&self) -> Result<String, JsValue> {
+        serialize_boc(&self.inner.clone().into_cell())
+    }
+
+    #[wasm_bindgen(js_name = "toBocBytes")]
+    pub fn to_boc_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        serialize_boc_bytes(&self.inner.clone().into_cell())
+    }
+}
+
+impl CellSlice {
+    fn load_biguint(&mut self, bits: usize) -> Result<BigUint, JsValue> {
+        let bytes = self.inner.get_next_bits(bits).handle_error()?;
+        // Bits are aligned to the left in the last byte
+        let padding = bytes.len() * 8 - bits;
+        Ok(BigUint::from_bytes_be(&bytes) >> padding)
+    }
+
+    fn load_var_len(&mut self, size: usize) -> Result<usize, JsValue> {
+        let len_bits = match size {
+            0 | 1 => return Err(CellError::InvalidVarIntSize).handle_error(),
+            size => (usize::BITS - (size - 1).leading_zeros()) as usize,
+        };
+        let len = self.inner.get_next_int(len_bits).handle_error()? as usize;
+        if len >= size {
+            return Err(CellError::InvalidVarIntSize).handle_error();
+        }
+        Ok(len)
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "string | number")]
+    pub type NumberLike;
+}
+
+#[derive(thiserror::Error, Debug)]
+enum CellError {
+    #[error("Not enough data")]
+    NotEnoughData,
+    #[error("Invalid var int size")]
+    InvalidVarIntSize,
+}
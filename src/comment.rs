@@ -0,0 +1,169 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::utils::*;
+
+/// Encodes the text as a plain comment. Encrypted comments are not supported,
+/// since there is no common format for them yet
+#[wasm_bindgen(js_name = "encodeComment")]
+pub fn encode_comment(text: &str) -> Result<String, JsValue> {
+    serialize_boc(&encode_plain_comment(text)?)
+}
+
+/// Returns `undefined` if the body is not a valid comment
+/// (unknown tag, malformed cells or text which is not UTF-8)
+#[wasm_bindgen(js_name = "decodeComment")]
+pub fn decode_comment(body: &Boc) -> Result<Option<DecodedComment>, JsValue> {
+    let body = parse_cell(body)?;
+    Ok(parse_plain_comment(&body).map(|text| {
+        ObjectBuilder::new()
+            .set("type", "plain")
+            .set("text", text)
+            .build()
+            .unchecked_into()
+    }))
+}
+
+pub fn encode_plain_comment(text: &str) -> Result<ton_types::Cell, JsValue> {
+    let mut root = ton_types::BuilderData::new();
+    root.append_u32(PLAIN_COMMENT_TAG).handle_error()?;
+    store_snake_data(root, text.as_bytes())
+}
+
+/// Returns plain comment text if the body contains one.
+///
+/// NOTE: this is called for every message body, so the tag is checked
+/// on the raw cell data before walking the snake cells
+pub fn parse_plain_comment(body: &ton_types::Cell) -> Option<String> {
+    if body.bit_length() < 32 || body.data()[..4] != PLAIN_COMMENT_TAG.to_be_bytes() {
+        return None;
+    }
+
+    let mut body = ton_types::SliceData::from(body.clone());
+    body.move_by(32).ok()?;
+    String::from_utf8(load_snake_data(body).ok()?).ok()
+}
+
+/// Stores bytes into the remaining space of the root cell,
+/// chaining the rest through the first reference of each cell
+fn store_snake_data(
+    mut root: ton_types::BuilderData,
+    data: &[u8],
+) -> Result<ton_types::Cell, JsValue> {
+    let head_len = std::cmp::min(data.len(), root.bits_free() / 8);
+    let (head, tail) = data.split_at(head_len);
+
+    let mut child = None;
+    for chunk in tail.chunks(MAX_CELL_BYTES).rev() {
+        let mut builder = ton_types::BuilderData::new();
+        builder.append_raw(chunk, chunk.len() * 8).handle_error()?;
+        if let Some(child) = child.take() {
+            builder.checked_append_reference(child).handle_error()?;
+        }
+        child = Some(builder.into_cell().handle_error()?);
+    }
+
+    root.append_raw(head, head.len() * 8).handle_error()?;
+    if let Some(child) = child {
+        root.checked_append_reference(child).handle_error()?;
+    }
+    root.into_cell().handle_error()
+}
+
+fn load_snake_data(mut slice: ton_types::SliceData) -> Result<Vec<u8>, JsValue> {
+    let mut data = Vec::new();
+    loop {
+        let bits = slice.remaining_bits();
+        if bits % 8 != 0 {
+            return Err(CommentError::InvalidSnakeData).handle_error();
+        }
+        data.extend(slice.get_next_bytes(bits / 8).handle_error()?);
+
+        match slice.remaining_references() {
+            0 => break,
+            1 => slice = slice.checked_drain_reference().handle_error()?.into(),
+            _ => return Err(CommentError::InvalidSnakeData).handle_error(),
+        }
+    }
+    Ok(data)
+}
+
+const PLAIN_COMMENT_TAG: u32 = 0x00000000;
+
+const MAX_CELL_BYTES: usize = 127;
+
+#[wasm_bindgen(typescript_custom_section)]
+const COMMENT: &str = r#"
+/**
+ * Plain comments use the standard `0x00000000` prefix with the text in snake cells.
+ */
+export type DecodedComment = { type: 'plain', text: string };
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "DecodedComment")]
+    pub type DecodedComment;
+}
+
+#[derive(thiserror::Error, Debug)]
+enum CommentError {
+    #[error("Invalid snake data")]
+    InvalidSnakeData,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_comment_round_trip() {
+        let texts = [
+            String::new(),
+            "hello".to_owned(),
+            "a".repeat(MAX_CELL_BYTES * 3 + 5),
+            "привет 👋".repeat(40),
+        ];
+        for text in texts {
+            let cell = encode_plain_comment(&text).unwrap();
+            assert_eq!(parse_plain_comment(&cell).as_deref(), Some(text.as_str()));
+        }
+    }
+
+    #[test]
+    fn snake_data_is_chained_through_references() {
+        let data = vec![0xab; MAX_CELL_BYTES * 2 + 10];
+        let cell = store_snake_data(ton_types::BuilderData::new(), &data).unwrap();
+
+        let mut depth = 0;
+        let mut current = cell.clone();
+        while current.references_count() > 0 {
+            assert_eq!(current.references_count(), 1);
+            current = current.reference(0).unwrap();
+            depth += 1;
+        }
+        assert_eq!(depth, 2);
+
+        assert_eq!(load_snake_data(cell.into()).unwrap(), data);
+    }
+
+    #[test]
+    fn invalid_utf8_is_not_a_comment() {
+        let mut root = ton_types::BuilderData::new();
+        root.append_u32(PLAIN_COMMENT_TAG).unwrap();
+        let cell = store_snake_data(root, &[0xff, 0xfe]).unwrap();
+        assert_eq!(parse_plain_comment(&cell), None);
+    }
+
+    #[test]
+    fn other_bodies_are_not_comments() {
+        let mut root = ton_types::BuilderData::new();
+        root.append_u32(0x12345678).unwrap();
+        let cell = store_snake_data(root, b"hello").unwrap();
+        assert_eq!(parse_plain_comment(&cell), None);
+
+        let mut root = ton_types::BuilderData::new();
+        root.append_u16(0).unwrap();
+        assert_eq!(parse_plain_comment(&root.into_cell().unwrap()), None);
+    }
+}
@@ -0,0 +1,577 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use nt::abi::FunctionExt;
+use nt::utils::Clock;
+use ton_block::Serializable;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::models::*;
+use crate::tokens_object::*;
+use crate::utils::*;
+
+#[wasm_bindgen]
+pub struct ContractAbi {
+    #[wasm_bindgen(skip)]
+    pub inner: ton_abi::Contract,
+}
+
+#[wasm_bindgen]
+impl ContractAbi {
+    #[wasm_bindgen(constructor)]
+    pub fn new(contract_abi: &str) -> Result<ContractAbi, JsValue> {
+        Ok(Self {
+            inner: parse_contract_abi(contract_abi)?,
+        })
+    }
+
+    #[wasm_bindgen(getter, js_name = "abiVersion")]
+    pub fn abi_version(&self) -> String {
+        let version = &self.inner.abi_version;
+        format!("{}.{}", version.major, version.minor)
+    }
+
+    #[wasm_bindgen(js_name = "functions")]
+    pub fn functions(&self) -> AbiFunctionsList {
+        let mut functions = self.inner.functions.values().collect::<Vec<_>>();
+        functions.sort_by(|a, b| a.name.cmp(&b.name));
+
+        functions
+            .into_iter()
+            .map(|function| {
+                ObjectBuilder::new()
+                    .set("name", &function.name)
+                    .set("inputs", make_abi_params_list(&function.inputs))
+                    .set("outputs", make_abi_params_list(&function.outputs))
+                    .set("inputId", function.input_id)
+                    .set("outputId", function.output_id)
+                    .build()
+            })
+            .collect::<js_sys::Array>()
+            .unchecked_into()
+    }
+
+    #[wasm_bindgen(js_name = "events")]
+    pub fn events(&self) -> AbiEventsList {
+        let mut events = self.inner.events.values().collect::<Vec<_>>();
+        events.sort_by(|a, b| a.name.cmp(&b.name));
+
+        events
+            .into_iter()
+            .map(|event| {
+                ObjectBuilder::new()
+                    .set("name", &event.name)
+                    .set("inputs", make_abi_params_list(&event.inputs))
+                    .set("id", event.id)
+                    .build()
+            })
+            .collect::<js_sys::Array>()
+            .unchecked_into()
+    }
+
+    #[wasm_bindgen(js_name = "data")]
+    pub fn data(&self) -> AbiDataList {
+        let mut data = self.inner.data.values().collect::<Vec<_>>();
+        data.sort_by_key(|item| item.key);
+
+        data.into_iter()
+            .map(|item| {
+                ObjectBuilder::new()
+                    .set("key", item.key as f64)
+                    .set("name", &item.value.name)
+                    .set("type", make_param_type_name(&item.value.kind))
+                    .set(
+                        "components",
+                        find_param_components(&item.value.kind).map(make_abi_params_list),
+                    )
+                    .build()
+            })
+            .collect::<js_sys::Array>()
+            .unchecked_into()
+    }
+
+    #[wasm_bindgen(js_name = "fields")]
+    pub fn fields(&self) -> ParamsList {
+        make_abi_params_list(&self.inner.fields)
+    }
+
+    #[wasm_bindgen(js_name = "generateTypings")]
+    pub fn generate_typings(&self) -> String {
+        crate::typings::generate_abi_typings(&self.inner)
+    }
+
+    /// Runs the function locally. Traced runs use a separate VM setup with
+    /// capabilities and gas limits from the blockchain config if specified
+    #[allow(clippy::too_many_arguments)]
+    #[wasm_bindgen(js_name = "runLocal")]
+    pub fn run_local(
+        &self,
+        clock: &ClockWithOffset,
+        account_stuff_boc: &Boc,
+        method: &str,
+        input: TokensObject,
+        responsible: bool,
+        trace: Option<bool>,
+        config: Option<Boc>,
+    ) -> Result<ExecutionOutput, JsValue> {
+        let account_stuff = parse_account_stuff(account_stuff_boc)?;
+        let method = self.inner.function(method).handle_error()?;
+        let input = parse_tokens_object(&method.inputs, input)?;
+
+        if trace.unwrap_or_default() {
+            let vm = crate::tvm::VmParams::new(config.as_ref(), account_stuff.addr.workchain_id())?;
+            let trace = crate::tvm::TraceLog::default();
+            let output = crate::tvm::run_local(
+                clock.inner.as_ref(),
+                &account_stuff,
+                method,
+                &input,
+                responsible,
+                &vm,
+                Some(&trace),
+            )?;
+
+            let output = make_execution_output(output)?;
+            js_sys::Reflect::set(&output, &JsValue::from_str("trace"), &trace.to_js())?;
+            return Ok(output);
+        }
+
+        let output = if responsible {
+            method
+                .run_local_responsible(clock.inner.as_ref(), account_stuff, &input)
+                .handle_error()?
+        } else {
+            method
+                .run_local(clock.inner.as_ref(), account_stuff, &input)
+                .handle_error()?
+        };
+
+        make_execution_output(output)
+    }
+
+    #[wasm_bindgen(js_name = "getExpectedAddress")]
+    pub fn get_expected_address(
+        &self,
+        tvc: &Boc,
+        workchain_id: i8,
+        public_key: Option<String>,
+        init_data: TokensObject,
+    ) -> Result<ExpectedAddress, JsValue> {
+        let mut state_init = parse_state_init(tvc)?;
+        let public_key = public_key.as_deref().map(parse_public_key).transpose()?;
+
+        state_init.data = if let Some(data) = state_init.data.take() {
+            Some(insert_init_data(&self.inner, data.into(), &public_key, init_data)?.into_cell())
+        } else {
+            None
+        };
+
+        let cell = state_init.serialize().handle_error()?;
+        let repr_hash = cell.repr_hash().to_hex_string();
+
+        Ok(ObjectBuilder::new()
+            .set("stateInit", serialize_boc(&cell)?)
+            .set("address", format!("{workchain_id}:{repr_hash}"))
+            .build()
+            .unchecked_into())
+    }
+
+    #[wasm_bindgen(js_name = "encodeInternalInput")]
+    pub fn encode_internal_input(
+        &self,
+        method: &str,
+        input: TokensObject,
+    ) -> Result<String, JsValue> {
+        serialize_boc(&self.encode_internal_input_cell(method, input)?)
+    }
+
+    #[wasm_bindgen(js_name = "encodeInternalInputBytes")]
+    pub fn encode_internal_input_bytes(
+        &self,
+        method: &str,
+        input: TokensObject,
+    ) -> Result<Vec<u8>, JsValue> {
+        serialize_boc_bytes(&self.encode_internal_input_cell(method, input)?)
+    }
+
+    #[wasm_bindgen(js_name = "decodeInput")]
+    pub fn decode_input(
+        &self,
+        message_body: &Boc,
+        method: MethodName,
+        internal: bool,
+    ) -> Result<Option<DecodedInput>, JsValue> {
+        let message_body = parse_cell_slice(message_body)?;
+        let method = parse_method_name(method)?;
+        let (method, data) =
+            match nt::abi::decode_input(&self.inner, message_body, &method, internal)
+                .handle_error()?
+            {
+                Some(method) => method,
+                None => return Ok(None),
+            };
+
+        Ok(Some(
+            ObjectBuilder::new()
+                .set("method", &method.name)
+                .set("input", make_tokens_object(data)?)
+                .build()
+                .unchecked_into(),
+        ))
+    }
+
+    #[wasm_bindgen(js_name = "decodeEvent")]
+    pub fn decode_event(
+        &self,
+        message_body: &Boc,
+        event: MethodName,
+    ) -> Result<Option<DecodedEvent>, JsValue> {
+        let message_body = parse_cell_slice(message_body)?;
+        let name = parse_method_name(event)?;
+        let (event, data) =
+            match nt::abi::decode_event(&self.inner, message_body, &name).handle_error()? {
+                Some(event) => event,
+                None => return Ok(None),
+            };
+
+        Ok(Some(
+            ObjectBuilder::new()
+                .set("event", &event.name)
+                .set("data", make_tokens_object(data)?)
+                .build()
+                .unchecked_into(),
+        ))
+    }
+
+    #[wasm_bindgen(js_name = "decodeOutput")]
+    pub fn decode_output(
+        &self,
+        message_body: &Boc,
+        method: MethodName,
+    ) -> Result<Option<DecodedOutput>, JsValue> {
+        let message_body = parse_cell_slice(message_body)?;
+        let method = parse_method_name(method)?;
+        let (method, data) =
+            match nt::abi::decode_output(&self.inner, message_body, &method).handle_error()? {
+                Some(method) => method,
+                None => return Ok(None),
+            };
+
+        Ok(Some(
+            ObjectBuilder::new()
+                .set("method", &method.name)
+                .set("output", make_tokens_object(data)?)
+                .build()
+                .unchecked_into(),
+        ))
+    }
+
+    #[wasm_bindgen(js_name = "decodeTransaction")]
+    pub fn decode_transaction(
+        &self,
+        transaction: Transaction,
+        method: MethodName,
+    ) -> Result<Option<DecodedTransaction>, JsValue> {
+        let transaction: JsValue = transaction.unchecked_into();
+        if !transaction.is_object() {
+            return Err(TokensJsonError::ObjectExpected).handle_error();
+        }
+
+        let method = parse_method_name(method)?;
+
+        let in_msg = js_sys::Reflect::get(&transaction, &JsValue::from_str("inMessage"))?;
+        if !in_msg.is_object() {
+            return Err(TokensJsonError::MessageExpected).handle_error();
+        }
+        let internal = js_sys::Reflect::get(&in_msg, &JsValue::from_str("src"))?.is_string();
+
+        let body_key = JsValue::from_str("body");
+        let in_msg_body = match js_sys::Reflect::get(&in_msg, &body_key)?.as_string() {
+            Some(body) => parse_cell_slice(&body)?,
+            None => return Ok(None),
+        };
+
+        let method =
+            match nt::abi::guess_method_by_input(&self.inner, &in_msg_body, &method, internal)
+                .handle_error()?
+            {
+                Some(method) => method,
+                None => return Ok(None),
+            };
+
+        let input = method.decode_input(in_msg_body, internal).handle_error()?;
+
+        let out_msgs = js_sys::Reflect::get(&transaction, &JsValue::from_str("outMessages"))?;
+        if !js_sys::Array::is_array(&out_msgs) {
+            return Err(TokensJsonError::ArrayExpected).handle_error();
+        }
+
+        let dst_key = JsValue::from_str("dst");
+        let ext_out_msgs = out_msgs
+            .unchecked_into::<js_sys::Array>()
+            .iter()
+            .filter_map(|message| {
+                match js_sys::Reflect::get(&message, &dst_key) {
+                    Ok(dst) if dst.is_string() => return None,
+                    Err(error) => return Some(Err(error)),
+                    _ => {}
+                };
+
+                Some(
+                    match js_sys::Reflect::get(&message, &body_key).map(|item| item.as_string()) {
+                        Ok(Some(body)) => parse_cell_slice(&body),
+                        Ok(None) => Err(TokensJsonError::MessageBodyExpected).handle_error(),
+                        Err(error) => Err(error),
+                    },
+                )
+            })
+            .collect::<Result<Vec<_>, JsValue>>()?;
+
+        let output = nt::abi::process_raw_outputs(&ext_out_msgs, method).handle_error()?;
+
+        Ok(Some(
+            ObjectBuilder::new()
+                .set("method", &method.name)
+                .set("input", make_tokens_object(input)?)
+                .set("output", make_tokens_object(output)?)
+                .build()
+                .unchecked_into(),
+        ))
+    }
+
+    #[wasm_bindgen(js_name = "decodeTransactionEvents")]
+    pub fn decode_transaction_events(
+        &self,
+        transaction: Transaction,
+    ) -> Result<DecodedTransactionEvents, JsValue> {
+        let transaction: JsValue = transaction.unchecked_into();
+        if !transaction.is_object() {
+            return Err(TokensJsonError::ObjectExpected).handle_error();
+        }
+
+        let out_msgs = js_sys::Reflect::get(&transaction, &JsValue::from_str("outMessages"))?;
+        if !js_sys::Array::is_array(&out_msgs) {
+            return Err(TokensJsonError::ArrayExpected).handle_error();
+        }
+
+        let body_key = JsValue::from_str("body");
+        let dst_key = JsValue::from_str("dst");
+        let ext_out_msgs = out_msgs
+            .unchecked_into::<js_sys::Array>()
+            .iter()
+            .filter_map(|message| {
+                match js_sys::Reflect::get(&message, &dst_key) {
+                    Ok(dst) if dst.is_string() => return None,
+                    Err(error) => return Some(Err(error)),
+                    _ => {}
+                };
+
+                Some(
+                    match js_sys::Reflect::get(&message, &body_key).map(|item| item.as_string()) {
+                        Ok(Some(body)) => parse_cell_slice(&body),
+                        Ok(None) => return None,
+                        Err(error) => Err(error),
+                    },
+                )
+            })
+            .collect::<Result<Vec<_>, JsValue>>()?;
+
+        let events = ext_out_msgs
+            .into_iter()
+            .filter_map(|body| {
+                let id = nt::abi::read_function_id(&body).ok()?;
+                let event = self.inner.event_by_id(id).ok()?;
+                let tokens = event.decode_input(body).ok()?;
+
+                let data = match make_tokens_object(tokens) {
+                    Ok(data) => data,
+                    Err(e) => return Some(Err(e)),
+                };
+
+                Some(Ok(ObjectBuilder::new()
+                    .set("event", &event.name)
+                    .set("data", data)
+                    .build()))
+            })
+            .collect::<Result<js_sys::Array, JsValue>>()?;
+
+        Ok(events.unchecked_into())
+    }
+
+    #[wasm_bindgen(js_name = "createExternalMessageWithoutSignature")]
+    pub fn create_external_message_without_signature(
+        &self,
+        clock: &ClockWithOffset,
+        dst: &str,
+        method: &str,
+        state_init: Option<Boc>,
+        input: TokensObject,
+        timeout: u32,
+    ) -> Result<SignedMessage, JsValue> {
+        use nt::core::models::{Expiration, ExpireAt};
+
+        // Parse params
+        let dst = parse_address(dst)?;
+        let method = self.inner.function(method).handle_error()?;
+        let input = parse_tokens_object(&method.inputs, input)?;
+
+        // Prepare headers
+        let time = clock.inner.now_ms_u64();
+        let expire_at = ExpireAt::new_from_millis(Expiration::Timeout(timeout), time);
+
+        let mut header = HashMap::with_capacity(3);
+        header.insert("time".to_string(), ton_abi::TokenValue::Time(time));
+        header.insert(
+            "expire".to_string(),
+            ton_abi::TokenValue::Expire(expire_at.timestamp),
+        );
+        header.insert("pubkey".to_string(), ton_abi::TokenValue::PublicKey(None));
+
+        // Encode body
+        let body = method
+            .encode_input(&header, &input, false, None, Some(dst.clone()))
+            .handle_error()?;
+
+        // Build message
+        let mut message =
+            ton_block::Message::with_ext_in_header(ton_block::ExternalInboundMessageHeader {
+                dst,
+                ..Default::default()
+            });
+        if let Some(state_init) = state_init {
+            message.set_state_init(parse_state_init(&state_init)?);
+        }
+        message.set_body(body.into());
+
+        // Serialize message
+        make_signed_message(nt::crypto::SignedMessage {
+            message,
+            expire_at: expire_at.timestamp,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[wasm_bindgen(js_name = "createExternalMessage")]
+    pub fn create_external_message(
+        &self,
+        clock: &ClockWithOffset,
+        dst: &str,
+        method: &str,
+        state_init: Option<Boc>,
+        input: TokensObject,
+        public_key: &str,
+        timeout: u32,
+    ) -> Result<UnsignedMessage, JsValue> {
+        let dst = parse_address(dst)?;
+        let method = self.inner.function(method).handle_error()?;
+        let input = parse_tokens_object(&method.inputs, input)?;
+        let public_key = parse_public_key(public_key)?;
+
+        let mut message =
+            ton_block::Message::with_ext_in_header(ton_block::ExternalInboundMessageHeader {
+                dst,
+                ..Default::default()
+            });
+        if let Some(state_init) = state_init {
+            message.set_state_init(parse_state_init(&state_init)?);
+        }
+
+        Ok(UnsignedMessage {
+            inner: nt::core::utils::make_labs_unsigned_message(
+                clock.inner.as_ref(),
+                message,
+                nt::core::models::Expiration::Timeout(timeout),
+                &public_key,
+                Cow::Owned(method.clone()),
+                input,
+            )
+            .handle_error()?,
+        })
+    }
+}
+
+impl ContractAbi {
+    fn encode_internal_input_cell(
+        &self,
+        method: &str,
+        input: TokensObject,
+    ) -> Result<ton_types::Cell, JsValue> {
+        let method = self.inner.function(method).handle_error()?;
+        let input = parse_tokens_object(&method.inputs, input)?;
+
+        method
+            .encode_internal_input(&input)
+            .and_then(|value| value.into_cell())
+            .handle_error()
+    }
+}
+
+fn make_abi_params_list(params: &[ton_abi::Param]) -> ParamsList {
+    params
+        .iter()
+        .map(make_abi_param)
+        .map(JsValue::from)
+        .collect::<js_sys::Array>()
+        .unchecked_into()
+}
+
+fn make_abi_param(param: &ton_abi::Param) -> AbiParam {
+    ObjectBuilder::new()
+        .set("name", &param.name)
+        .set("type", make_param_type_name(&param.kind))
+        .set(
+            "components",
+            find_param_components(&param.kind).map(make_abi_params_list),
+        )
+        .build()
+        .unchecked_into()
+}
+
+fn find_param_components(kind: &ton_abi::ParamType) -> Option<&[ton_abi::Param]> {
+    match kind {
+        ton_abi::ParamType::Tuple(params) => Some(params),
+        ton_abi::ParamType::Array(kind)
+        | ton_abi::ParamType::FixedArray(kind, _)
+        | ton_abi::ParamType::Optional(kind)
+        | ton_abi::ParamType::Ref(kind)
+        | ton_abi::ParamType::Map(_, kind) => find_param_components(kind),
+        _ => None,
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const ABI_ENTITIES: &str = r#"
+export type AbiFunction = {
+  name: string;
+  inputs: AbiParam[];
+  outputs: AbiParam[];
+  inputId: number;
+  outputId: number;
+};
+
+export type AbiEvent = {
+  name: string;
+  inputs: AbiParam[];
+  id: number;
+};
+
+export type AbiDataItem = AbiParam & {
+  key: number;
+};
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "AbiParam")]
+    pub type AbiParam;
+
+    #[wasm_bindgen(typescript_type = "Array<AbiFunction>")]
+    pub type AbiFunctionsList;
+
+    #[wasm_bindgen(typescript_type = "Array<AbiEvent>")]
+    pub type AbiEventsList;
+
+    #[wasm_bindgen(typescript_type = "Array<AbiDataItem>")]
+    pub type AbiDataList;
+}
@@ -0,0 +1,329 @@
+use chacha20poly1305::aead::Aead;
+use chacha20poly1305::{ChaCha20Poly1305, KeyInit};
+use rand::Rng;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+use zeroize::Zeroize;
+
+use crate::models::*;
+use crate::tokens_object::*;
+use crate::utils::*;
+
+#[wasm_bindgen(js_name = "generateMnemonic")]
+pub fn generate_mnemonic(mnemonic_type: MnemonicKind) -> Result<String, JsValue> {
+    let mnemonic_type = match parse_mnemonic_kind(mnemonic_type)? {
+        MnemonicKindValue::Bip39 => nt::crypto::MnemonicType::Labs(0),
+        MnemonicKindValue::Legacy => nt::crypto::MnemonicType::Legacy,
+    };
+    Ok(nt::crypto::generate_key(mnemonic_type).words.join(" "))
+}
+
+#[wasm_bindgen(js_name = "validateMnemonic")]
+pub fn validate_mnemonic(phrase: &str, mnemonic_type: MnemonicKind) -> Result<bool, JsValue> {
+    Ok(match parse_mnemonic_kind(mnemonic_type)? {
+        MnemonicKindValue::Bip39 => {
+            bip39::Mnemonic::validate(phrase.trim(), bip39::Language::English).is_ok()
+        }
+        // NOTE: legacy phrases are validated by the derivation itself
+        MnemonicKindValue::Legacy => {
+            nt::crypto::derive_from_phrase(phrase.trim(), nt::crypto::MnemonicType::Legacy).is_ok()
+        }
+    })
+}
+
+#[wasm_bindgen(js_name = "deriveKeyPair")]
+pub fn derive_key_pair(
+    phrase: &str,
+    params: DeriveKeyPairParams,
+) -> Result<Ed25519KeyPair, JsValue> {
+    let phrase = phrase.trim();
+
+    let params: JsValue = params.into();
+    if !params.is_object() {
+        return Err(TokensJsonError::ObjectExpected).handle_error();
+    }
+
+    let mnemonic_type = js_sys::Reflect::get(&params, &JsValue::from_str("type"))?;
+    let key_pair = match parse_mnemonic_kind(mnemonic_type.into())? {
+        MnemonicKindValue::Legacy => {
+            nt::crypto::derive_from_phrase(phrase, nt::crypto::MnemonicType::Legacy)
+                .handle_error()?
+        }
+        MnemonicKindValue::Bip39 => {
+            let path = js_sys::Reflect::get(&params, &JsValue::from_str("path"))?;
+            let account_id = js_sys::Reflect::get(&params, &JsValue::from_str("accountId"))?;
+
+            if let Some(path) = path.as_string() {
+                derive_bip39_key_pair(phrase, &path)?
+            } else if !path.is_undefined() && !path.is_null() {
+                return Err(TokensJsonError::StringExpected).handle_error();
+            } else {
+                let account_id = parse_account_id(account_id)?;
+                nt::crypto::derive_from_phrase(phrase, nt::crypto::MnemonicType::Labs(account_id))
+                    .handle_error()?
+            }
+        }
+    };
+
+    Ok(make_ed25519_key_pair(key_pair))
+}
+
+fn derive_bip39_key_pair(phrase: &str, path: &str) -> Result<ed25519_dalek::Keypair, JsValue> {
+    let mnemonic = bip39::Mnemonic::from_phrase(phrase, bip39::Language::English).handle_error()?;
+    let seed = bip39::Seed::new(&mnemonic, "");
+
+    let derived = tiny_hderive::bip32::ExtendedPrivKey::derive(seed.as_bytes(), path)
+        .map_err(|_| CryptoError::InvalidDerivationPath)
+        .handle_error()?;
+
+    let mut secret_bytes = derived.secret();
+    let secret = ed25519_dalek::SecretKey::from_bytes(&secret_bytes).handle_error();
+    secret_bytes.zeroize();
+
+    let secret = secret?;
+    let public = ed25519_dalek::PublicKey::from(&secret);
+    Ok(ed25519_dalek::Keypair { secret, public })
+}
+
+/// Encrypts raw bytes for each recipient. Only ChaCha20Poly1305 is supported
+#[wasm_bindgen(js_name = "encryptData")]
+pub fn encrypt_data(
+    secret_key: &str,
+    recipient_public_keys: Vec<JsValue>,
+    algorithm: EncryptionAlgorithm,
+    data: &[u8],
+) -> Result<EncryptedDataList, JsValue> {
+    check_encryption_algorithm(algorithm.into())?;
+
+    let secret = parse_secret_key(secret_key)?;
+    let source_public_key = ed25519_dalek::PublicKey::from(&secret);
+
+    recipient_public_keys
+        .into_iter()
+        .map(|public_key| {
+            let recipient_public_key = match public_key.as_string() {
+                Some(public_key) => parse_public_key(&public_key)?,
+                None => return Err(TokensJsonError::StringExpected).handle_error(),
+            };
+
+            let (nonce, encrypted) = encrypt_bytes(&secret, &recipient_public_key, data)?;
+
+            Ok(ObjectBuilder::new()
+                .set("algorithm", CHACHA20_POLY1305)
+                .set("sourcePublicKey", hex::encode(source_public_key.as_bytes()))
+                .set(
+                    "recipientPublicKey",
+                    hex::encode(recipient_public_key.as_bytes()),
+                )
+                .set("data", base64::encode(encrypted))
+                .set("nonce", base64::encode(nonce))
+                .build())
+        })
+        .collect::<Result<js_sys::Array, JsValue>>()
+        .map(JsCast::unchecked_into)
+}
+
+/// Decrypts the data from `encryptData` into the original bytes
+#[wasm_bindgen(js_name = "decryptData")]
+pub fn decrypt_data(secret_key: &str, encrypted_data: EncryptedData) -> Result<Vec<u8>, JsValue> {
+    let encrypted_data: JsValue = encrypted_data.into();
+    if !encrypted_data.is_object() {
+        return Err(TokensJsonError::ObjectExpected).handle_error();
+    }
+
+    let get_string = |name: &str| -> Result<String, JsValue> {
+        match js_sys::Reflect::get(&encrypted_data, &JsValue::from_str(name))?.as_string() {
+            Some(value) => Ok(value),
+            None => Err(TokensJsonError::ParameterNotFound(name.to_owned())).handle_error(),
+        }
+    };
+
+    check_encryption_algorithm(js_sys::Reflect::get(
+        &encrypted_data,
+        &JsValue::from_str("algorithm"),
+    )?)?;
+
+    let source_public_key = parse_public_key(&get_string("sourcePublicKey")?)?;
+    let recipient_public_key = parse_public_key(&get_string("recipientPublicKey")?)?;
+    let data = base64::decode(get_string("data")?.trim()).handle_error()?;
+    let nonce = base64::decode(get_string("nonce")?.trim()).handle_error()?;
+
+    let secret = parse_secret_key(secret_key)?;
+    decrypt_bytes(
+        &secret,
+        &source_public_key,
+        &recipient_public_key,
+        &nonce,
+        &data,
+    )
+}
+
+fn check_encryption_algorithm(algorithm: JsValue) -> Result<(), JsValue> {
+    match algorithm.as_string().as_deref() {
+        Some(CHACHA20_POLY1305) => Ok(()),
+        _ => Err(CryptoError::UnsupportedAlgorithm).handle_error(),
+    }
+}
+
+pub fn encrypt_bytes(
+    secret: &ed25519_dalek::SecretKey,
+    recipient_public_key: &ed25519_dalek::PublicKey,
+    data: &[u8],
+) -> Result<([u8; NONCE_LEN], Vec<u8>), JsValue> {
+    let cipher = make_cipher(secret, recipient_public_key);
+
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill(&mut nonce);
+
+    let encrypted = cipher
+        .encrypt(chacha20poly1305::Nonce::from_slice(&nonce), data)
+        .map_err(|_| CryptoError::EncryptionFailed)
+        .handle_error()?;
+
+    Ok((nonce, encrypted))
+}
+
+pub fn decrypt_bytes(
+    secret: &ed25519_dalek::SecretKey,
+    source_public_key: &ed25519_dalek::PublicKey,
+    recipient_public_key: &ed25519_dalek::PublicKey,
+    nonce: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, JsValue> {
+    if nonce.len() != NONCE_LEN {
+        return Err(CryptoError::InvalidNonce).handle_error();
+    }
+
+    let public_key = ed25519_dalek::PublicKey::from(secret);
+
+    // NOTE: both sides of the exchange are able to decrypt the data
+    let other_public_key = if &public_key == recipient_public_key {
+        source_public_key
+    } else if &public_key == source_public_key {
+        recipient_public_key
+    } else {
+        return Err(CryptoError::PublicKeyMismatch).handle_error();
+    };
+
+    make_cipher(secret, other_public_key)
+        .decrypt(chacha20poly1305::Nonce::from_slice(nonce), data)
+        .map_err(|_| CryptoError::DecryptionFailed)
+        .handle_error()
+}
+
+fn make_cipher(
+    secret: &ed25519_dalek::SecretKey,
+    public_key: &ed25519_dalek::PublicKey,
+) -> ChaCha20Poly1305 {
+    let mut shared_secret = nt::crypto::x25519::compute_shared(secret, public_key);
+    let cipher = ChaCha20Poly1305::new(chacha20poly1305::Key::from_slice(&shared_secret));
+    shared_secret.zeroize();
+    cipher
+}
+
+pub fn parse_secret_key(secret_key: &str) -> Result<ed25519_dalek::SecretKey, JsValue> {
+    let mut secret_key = parse_hex_or_base64_bytes(secret_key).handle_error()?;
+    let secret = ed25519_dalek::SecretKey::from_bytes(&secret_key).handle_error();
+    secret_key.zeroize();
+    secret
+}
+
+pub const NONCE_LEN: usize = 12;
+
+const CHACHA20_POLY1305: &str = "ChaCha20Poly1305";
+
+enum MnemonicKindValue {
+    Bip39,
+    Legacy,
+}
+
+fn parse_mnemonic_kind(kind: MnemonicKind) -> Result<MnemonicKindValue, JsValue> {
+    match JsValue::from(kind).as_string().as_deref() {
+        Some("bip39") => Ok(MnemonicKindValue::Bip39),
+        Some("legacy") => Ok(MnemonicKindValue::Legacy),
+        _ => Err(CryptoError::InvalidMnemonicType).handle_error(),
+    }
+}
+
+/// Parses `{ type: 'legacy' } | { type: 'bip39', accountId?: number }`
+pub(crate) fn parse_mnemonic_type(value: &JsValue) -> Result<nt::crypto::MnemonicType, JsValue> {
+    if !value.is_object() {
+        return Err(TokensJsonError::ObjectExpected).handle_error();
+    }
+
+    let kind = js_sys::Reflect::get(value, &JsValue::from_str("type"))?;
+    Ok(match parse_mnemonic_kind(kind.into())? {
+        MnemonicKindValue::Legacy => nt::crypto::MnemonicType::Legacy,
+        MnemonicKindValue::Bip39 => {
+            let account_id = js_sys::Reflect::get(value, &JsValue::from_str("accountId"))?;
+            nt::crypto::MnemonicType::Labs(parse_account_id(account_id)?)
+        }
+    })
+}
+
+fn parse_account_id(account_id: JsValue) -> Result<u16, JsValue> {
+    match account_id.as_f64() {
+        Some(id) if id.fract() == 0.0 && (0.0..=u16::MAX as f64).contains(&id) => Ok(id as u16),
+        Some(id) => Err(TokensJsonError::InvalidNumber(id.to_string())).handle_error(),
+        None if account_id.is_undefined() || account_id.is_null() => Ok(0),
+        None => Err(TokensJsonError::NumberExpected).handle_error(),
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const MNEMONIC: &str = r#"
+export type MnemonicType = 'bip39' | 'legacy';
+
+export type DeriveKeyPairParams =
+    | { type: 'legacy' }
+    | { type: 'bip39', accountId?: number, path?: string };
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const ENCRYPTED_DATA: &str = r#"
+export type EncryptionAlgorithm = 'ChaCha20Poly1305';
+
+export type EncryptedData = {
+    algorithm: EncryptionAlgorithm,
+    sourcePublicKey: string,
+    recipientPublicKey: string,
+    data: string,
+    nonce: string,
+};
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "MnemonicType")]
+    pub type MnemonicKind;
+
+    #[wasm_bindgen(typescript_type = "DeriveKeyPairParams")]
+    pub type DeriveKeyPairParams;
+
+    #[wasm_bindgen(typescript_type = "EncryptionAlgorithm")]
+    pub type EncryptionAlgorithm;
+
+    #[wasm_bindgen(typescript_type = "EncryptedData")]
+    pub type EncryptedData;
+
+    #[wasm_bindgen(typescript_type = "Array<EncryptedData>")]
+    pub type EncryptedDataList;
+}
+
+#[derive(thiserror::Error, Debug)]
+enum CryptoError {
+    #[error("Invalid mnemonic type")]
+    InvalidMnemonicType,
+    #[error("Invalid derivation path")]
+    InvalidDerivationPath,
+    #[error("Unsupported encryption algorithm, only ChaCha20Poly1305 is available")]
+    UnsupportedAlgorithm,
+    #[error("Invalid nonce")]
+    InvalidNonce,
+    #[error("Public key mismatch")]
+    PublicKeyMismatch,
+    #[error("Failed to encrypt data")]
+    EncryptionFailed,
+    #[error("Failed to decrypt data")]
+    DecryptionFailed,
+}
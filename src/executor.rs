@@ -0,0 +1,1038 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use num_traits::ToPrimitive;
+use ton_block::{Deserializable, GetRepresentationHash, Serializable};
+use ton_executor::TransactionExecutor;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::*;
+
+use crate::models::*;
+use crate::tokens_object::*;
+use crate::transport::TransportHandle;
+use crate::utils::*;
+
+#[wasm_bindgen(js_name = "executeLocal")]
+pub fn execute_local(
+    config: &Boc,
+    account: &Boc,
+    message: &Boc,
+    utime: u32,
+    disable_signature_check: bool,
+    options: Option<ExecuteLocalOptions>,
+) -> Result<TransactionExecutorOutput, JsValue> {
+    let options = parse_execute_local_options(options)?;
+
+    let mut account = parse_cell(account)?;
+    let mut account_state =
+        ton_block::Account::construct_from_cell(account.clone()).handle_error()?;
+    let last_trans_lt = match options.last_trans_lt {
+        Some(last_trans_lt) => last_trans_lt,
+        None => account_state.last_tr_time().unwrap_or_default(),
+    };
+    if let Some(grams) = options.override_balance {
+        let mut balance = match account_state.balance() {
+            Some(balance) => balance.clone(),
+            None => return Err(ExecutorError::BalanceOverrideForEmptyAccount).handle_error(),
+        };
+        balance.grams = grams;
+        account_state.set_balance(balance);
+        account = account_state.serialize().handle_error()?;
+    }
+
+    let message = ton_block::Message::construct_from_cell(parse_cell(message)?).handle_error()?;
+    let config =
+        ton_block::ConfigParams::construct_from_cell(parse_cell(config)?).handle_error()?;
+    let config = ton_executor::BlockchainConfig::with_config(config).handle_error()?;
+
+    let mut executor = ton_executor::OrdinaryTransactionExecutor::new(config);
+    executor.set_signature_check_disabled(disable_signature_check);
+
+    let trace = options.trace.then(crate::tvm::TraceLog::default);
+    let trace_callback = trace.as_ref().map(|trace| {
+        let callback: Arc<ton_vm::executor::TraceCallback> = Arc::new(trace.make_callback());
+        callback
+    });
+
+    let params = ton_executor::ExecuteParams {
+        state_libs: ton_types::HashmapE::with_hashmap(256, options.libraries),
+        block_unixtime: utime,
+        block_lt: options.block_lt.unwrap_or(last_trans_lt + 10),
+        last_tr_lt: Arc::new(AtomicU64::new(last_trans_lt)),
+        seed_block: options.seed_block.unwrap_or_default(),
+        debug: options.debug,
+        trace_callback,
+        ..Default::default()
+    };
+
+    let tx = match executor.execute_with_libs_and_params(Some(&message), &mut account, params) {
+        Ok(tx) => tx,
+        Err(e) => {
+            return match e.downcast_ref::<ton_executor::ExecutorError>() {
+                Some(ton_executor::ExecutorError::NoAcceptError(code, arg)) => {
+                    Ok(make_no_accept_output(*code, parse_result_arg(arg.as_ref()))
+                        .set("trace", trace.as_ref().map(|trace| trace.to_js()))
+                        .build()
+                        .unchecked_into())
+                }
+                _ => Err(e).handle_error(),
+            }
+        }
+    };
+
+    Ok(make_executed_transaction(tx)?
+        .set("account", serialize_boc(&account)?)
+        .set("trace", trace.as_ref().map(|trace| trace.to_js()))
+        .build()
+        .unchecked_into())
+}
+
+#[wasm_bindgen(js_name = "emulateMessageTree")]
+pub fn emulate_message_tree(
+    config: &Boc,
+    accounts: Option<AccountsMap>,
+    message: &Boc,
+    utime: u32,
+    options: Option<EmulateMessageTreeOptions>,
+) -> Result<PromiseEmulatedMessageTree, JsValue> {
+    let message = parse_message(message)?;
+    let emulator = MessageTreeEmulator::new(config, accounts, message, utime, options)?;
+    Ok(JsCast::unchecked_into(future_to_promise(emulator.run())))
+}
+
+pub struct MessageTreeEmulator {
+    executor: ton_executor::OrdinaryTransactionExecutor,
+    accounts: HashMap<ton_block::MsgAddressInt, ton_types::Cell>,
+    message: ton_block::Message,
+    utime: u32,
+    max_depth: u32,
+    max_transactions: u32,
+    block_lt: Option<u64>,
+    fetch_account: Option<js_sys::Function>,
+    transport: Option<TransportHandle>,
+}
+
+impl MessageTreeEmulator {
+    pub fn new(
+        config: &Boc,
+        accounts: Option<AccountsMap>,
+        message: ton_block::Message,
+        utime: u32,
+        options: Option<EmulateMessageTreeOptions>,
+    ) -> Result<Self, JsValue> {
+        let config =
+            ton_block::ConfigParams::construct_from_cell(parse_cell(config)?).handle_error()?;
+        let config = ton_executor::BlockchainConfig::with_config(config).handle_error()?;
+        let accounts = parse_accounts_map(accounts)?;
+        Self::with_config(config, accounts, message, utime, options)
+    }
+
+    pub fn with_config(
+        config: ton_executor::BlockchainConfig,
+        accounts: HashMap<ton_block::MsgAddressInt, ton_types::Cell>,
+        message: ton_block::Message,
+        utime: u32,
+        options: Option<EmulateMessageTreeOptions>,
+    ) -> Result<Self, JsValue> {
+        let options: Option<JsValue> = match options {
+            Some(options) if options.is_object() => Some(options.unchecked_into()),
+            Some(_) => return Err(TokensJsonError::ObjectExpected).handle_error(),
+            None => None,
+        };
+
+        let mut max_depth = DEFAULT_MAX_DEPTH;
+        let mut max_transactions = DEFAULT_MAX_TRANSACTIONS;
+        let mut disable_signature_check = false;
+        let mut block_lt = None;
+        let mut fetch_account = None;
+        if let Some(options) = &options {
+            if let Some(value) = parse_optional_u64(options, "maxDepth")? {
+                max_depth = value.min(u32::MAX as u64) as u32;
+            }
+            if let Some(value) = parse_optional_u64(options, "maxTransactions")? {
+                max_transactions = value.min(u32::MAX as u64) as u32;
+            }
+            disable_signature_check = parse_optional_bool(options, "disableSignatureCheck")?;
+            block_lt = parse_optional_u64(options, "blockLt")?;
+            fetch_account = match get_optional_field(options, "fetchAccount")? {
+                Some(value) => match value.dyn_into::<js_sys::Function>() {
+                    Ok(value) => Some(value),
+                    Err(_) => return Err(ExecutorError::FunctionExpected).handle_error(),
+                },
+                None => None,
+            };
+        }
+
+        let mut executor = ton_executor::OrdinaryTransactionExecutor::new(config);
+        executor.set_signature_check_disabled(disable_signature_check);
+
+        Ok(Self {
+            executor,
+            accounts,
+            message,
+            utime,
+            max_depth,
+            max_transactions,
+            block_lt,
+            fetch_account,
+            transport: None,
+        })
+    }
+
+    pub fn with_transport(mut self, transport: TransportHandle) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    pub async fn run(mut self) -> Result<JsValue, JsValue> {
+        let (transactions, failed_messages, pending_messages) = match self.execute().await? {
+            MessageTreeOutput::Rejected {
+                exit_code,
+                result_arg,
+            } => return Ok(make_no_accept_output(exit_code, result_arg).build()),
+            MessageTreeOutput::Executed {
+                transactions,
+                failed_messages,
+                pending_messages,
+            } => (transactions, failed_messages, pending_messages),
+        };
+
+        let accounts = js_sys::Map::new();
+        for (address, account) in &self.accounts {
+            accounts.set(
+                &JsValue::from(address.to_string()),
+                &JsValue::from(serialize_boc(account)?),
+            );
+        }
+
+        Ok(ObjectBuilder::new()
+            .set(
+                "transactions",
+                transactions
+                    .into_iter()
+                    .map(|item| {
+                        Ok(make_executed_transaction(item.transaction)?
+                            .set("depth", item.depth)
+                            .set("parent", item.parent)
+                            .build())
+                    })
+                    .collect::<Result<js_sys::Array, JsValue>>()?,
+            )
+            .set("accounts", accounts)
+            .set(
+                "failedMessages",
+                failed_messages
+                    .into_iter()
+                    .map(|item| {
+                        Ok(ObjectBuilder::new()
+                            .set("dst", item.dst.to_string())
+                            .set(
+                                "boc",
+                                serialize_boc(&item.message.serialize().handle_error()?)?,
+                            )
+                            .set("error", item.error)
+                            .set("depth", item.depth)
+                            .set("parent", item.parent)
+                            .build())
+                    })
+                    .collect::<Result<js_sys::Array, JsValue>>()?,
+            )
+            .set(
+                "pendingMessages",
+                pending_messages
+                    .into_iter()
+                    .map(|item| {
+                        Ok(ObjectBuilder::new()
+                            .set("dst", item.dst.to_string())
+                            .set(
+                                "boc",
+                                serialize_boc(&item.message.serialize().handle_error()?)?,
+                            )
+                            .set("depth", item.depth)
+                            .set("parent", item.parent)
+                            .build())
+                    })
+                    .collect::<Result<js_sys::Array, JsValue>>()?,
+            )
+            .build())
+    }
+
+    pub async fn execute(&mut self) -> Result<MessageTreeOutput, JsValue> {
+        let mut max_lt = 0;
+        for account in self.accounts.values() {
+            max_lt = max_lt.max(get_last_trans_lt(account)?);
+        }
+        let last_tr_lt = Arc::new(AtomicU64::new(max_lt + 10));
+
+        let mut transactions = Vec::new();
+        let mut failed_messages = Vec::new();
+        let mut pending_messages = Vec::new();
+
+        let mut queue = VecDeque::new();
+        queue.push_back((self.message.clone(), 0, None::<u32>));
+
+        while let Some((message, depth, parent)) = queue.pop_front() {
+            let dst = match message.dst() {
+                Some(dst) => dst,
+                None => continue,
+            };
+
+            if depth > self.max_depth || transactions.len() as u32 >= self.max_transactions {
+                pending_messages.push(PendingTreeMessage {
+                    message,
+                    dst,
+                    depth,
+                    parent,
+                });
+                continue;
+            }
+
+            let mut account = self.get_account(&dst).await?;
+            // NOTE: fetched accounts may be newer than the provided ones
+            last_tr_lt.fetch_max(get_last_trans_lt(&account)? + 10, Ordering::AcqRel);
+
+            let params = ton_executor::ExecuteParams {
+                block_unixtime: self.utime,
+                block_lt: self
+                    .block_lt
+                    .unwrap_or_else(|| last_tr_lt.load(Ordering::Acquire)),
+                last_tr_lt: last_tr_lt.clone(),
+                ..Default::default()
+            };
+
+            let transaction = match self.executor.execute_with_libs_and_params(
+                Some(&message),
+                &mut account,
+                params,
+            ) {
+                Ok(tx) => tx,
+                Err(e) if parent.is_some() => {
+                    failed_messages.push(FailedTreeMessage {
+                        message,
+                        dst,
+                        error: e.to_string(),
+                        depth,
+                        parent,
+                    });
+                    continue;
+                }
+                Err(e) => {
+                    return match e.downcast_ref::<ton_executor::ExecutorError>() {
+                        // Only the root external message can be rejected
+                        Some(ton_executor::ExecutorError::NoAcceptError(code, arg)) => {
+                            Ok(MessageTreeOutput::Rejected {
+                                exit_code: *code,
+                                result_arg: parse_result_arg(arg.as_ref()),
+                            })
+                        }
+                        _ => Err(e).handle_error(),
+                    };
+                }
+            };
+            self.accounts.insert(dst, account);
+
+            let mut out_messages = Vec::new();
+            transaction
+                .iterate_out_msgs(|message| {
+                    if message.is_internal() {
+                        out_messages.push(message);
+                    }
+                    Ok(true)
+                })
+                .handle_error()?;
+
+            let index = transactions.len() as u32;
+            queue.extend(
+                out_messages
+                    .into_iter()
+                    .map(|message| (message, depth + 1, Some(index))),
+            );
+
+            transactions.push(TreeTransaction {
+                transaction,
+                depth,
+                parent,
+            });
+        }
+
+        Ok(MessageTreeOutput::Executed {
+            transactions,
+            failed_messages,
+            pending_messages,
+        })
+    }
+
+    async fn get_account(
+        &self,
+        address: &ton_block::MsgAddressInt,
+    ) -> Result<ton_types::Cell, JsValue> {
+        if let Some(account) = self.accounts.get(address) {
+            return Ok(account.clone());
+        }
+
+        if let Some(fetch_account) = &self.fetch_account {
+            let result =
+                fetch_account.call1(&JsValue::undefined(), &JsValue::from(address.to_string()))?;
+            let result = match result.dyn_into::<js_sys::Promise>() {
+                Ok(promise) => JsFuture::from(promise).await?,
+                Err(result) => result,
+            };
+            if !result.is_null() && !result.is_undefined() {
+                return parse_cell(result.unchecked_ref::<Boc>());
+            }
+        }
+
+        if let Some(transport) = &self.transport {
+            let state = transport
+                .as_ref()
+                .get_contract_state(address)
+                .await
+                .handle_error()?;
+            if let nt::transport::models::RawContractState::Exists(state) = state {
+                return ton_block::Account::Account(state.account)
+                    .serialize()
+                    .handle_error();
+            }
+        }
+
+        ton_block::Account::AccountNone.serialize().handle_error()
+    }
+}
+
+#[wasm_bindgen(js_name = "estimateFees")]
+pub fn estimate_fees(
+    config: &Boc,
+    accounts: Option<AccountsMap>,
+    message: &EstimatedMessage,
+    utime: u32,
+    options: Option<EstimateFeesOptions>,
+) -> Result<PromiseEstimatedFees, JsValue> {
+    let include_chain = parse_include_chain(options.as_ref())?;
+    let message = parse_estimated_message(message)?;
+    let emulator = MessageTreeEmulator::new(
+        config,
+        accounts,
+        message,
+        utime,
+        options.map(JsCast::unchecked_into),
+    )?;
+    Ok(JsCast::unchecked_into(future_to_promise(
+        emulator.estimate_fees(include_chain),
+    )))
+}
+
+impl MessageTreeEmulator {
+    pub async fn estimate_fees(mut self, include_chain: bool) -> Result<JsValue, JsValue> {
+        if !include_chain {
+            self.max_depth = 0;
+        }
+
+        let (transactions, failed_messages, pending_messages) = match self.execute().await? {
+            MessageTreeOutput::Rejected {
+                exit_code,
+                result_arg,
+            } => return Ok(make_no_accept_output(exit_code, result_arg).build()),
+            MessageTreeOutput::Executed {
+                transactions,
+                failed_messages,
+                pending_messages,
+            } => (transactions, failed_messages, pending_messages),
+        };
+
+        let mut root = None;
+        let mut chain = TransactionFees::default();
+        let mut nodes =
+            Vec::with_capacity(transactions.len() + failed_messages.len() + pending_messages.len());
+        for item in &transactions {
+            let fees = TransactionFees::compute(&item.transaction)?;
+            let in_msg = item.transaction.read_in_msg().handle_error()?;
+            let (value, fwd_fee) = in_msg.as_ref().map(get_message_value).unwrap_or_default();
+            nodes.push(FeeNode {
+                parent: item.parent.map(|parent| parent as usize),
+                value,
+                fwd_fee,
+                fees: Some(fees.total_fees),
+            });
+            chain.append(&fees);
+            if root.is_none() {
+                root = Some(fees);
+            }
+        }
+
+        let root = match root {
+            Some(root) => root,
+            None => return Err(ExecutorError::NoTransactionsProduced).handle_error(),
+        };
+
+        let unexecuted = failed_messages
+            .iter()
+            .map(|item| (&item.message, item.parent))
+            .chain(
+                pending_messages
+                    .iter()
+                    .map(|item| (&item.message, item.parent)),
+            );
+        for (message, parent) in unexecuted {
+            let (value, fwd_fee) = get_message_value(message);
+            nodes.push(FeeNode {
+                parent: parent.map(|parent| parent as usize),
+                value,
+                fwd_fee,
+                fees: None,
+            });
+        }
+        let recommended_value = compute_recommended_value(&nodes, self.message.is_internal());
+
+        let mut result = ObjectBuilder::new().set("root", root.make_js());
+        if include_chain {
+            result = result
+                .set("chain", chain.make_js())
+                .set("transactionCount", transactions.len() as u32)
+                .set("recommendedValue", recommended_value.to_string())
+                .set(
+                    "complete",
+                    pending_messages.is_empty() && failed_messages.is_empty(),
+                );
+        }
+        Ok(result.build())
+    }
+}
+
+pub fn parse_include_chain(options: Option<&EstimateFeesOptions>) -> Result<bool, JsValue> {
+    match options {
+        Some(options) if options.is_object() => parse_optional_bool(options, "includeChain"),
+        Some(_) => Err(TokensJsonError::ObjectExpected).handle_error(),
+        None => Ok(false),
+    }
+}
+
+pub fn parse_message(message: &Boc) -> Result<ton_block::Message, JsValue> {
+    ton_block::Message::construct_from_cell(parse_cell(message)?).handle_error()
+}
+
+/// Parses either a raw message BOC or a `SignedMessage` object
+pub fn parse_estimated_message(message: &EstimatedMessage) -> Result<ton_block::Message, JsValue> {
+    if message.is_string() || message.is_instance_of::<js_sys::Uint8Array>() {
+        parse_message(message.unchecked_ref())
+    } else {
+        parse_signed_message(message.clone().unchecked_into()).map(|signed| signed.message)
+    }
+}
+
+/// Message in the emulated tree, as seen by the fee estimation
+struct FeeNode {
+    /// Index of the transaction which produced this message
+    parent: Option<usize>,
+    /// Attached value
+    value: u128,
+    /// Forwarding fee paid by the sender
+    fwd_fee: u128,
+    /// Total fees of the transaction, `None` if the message was not executed
+    fees: Option<u128>,
+}
+
+/// Computes the inbound value required by the root transaction
+/// to deliver all outgoing messages in the tree.
+///
+/// Nodes must be in topological order (parents before children).
+fn compute_recommended_value(nodes: &[FeeNode], root_is_internal: bool) -> u128 {
+    let mut required = vec![0u128; nodes.len()];
+    for (i, node) in nodes.iter().enumerate().rev() {
+        let need = match node.fees {
+            Some(fees) => fees + required[i],
+            None => 0,
+        };
+        if let Some(parent) = node.parent {
+            required[parent] += node.fwd_fee + node.value.max(need);
+        }
+    }
+
+    match nodes.first() {
+        // External messages carry no value, so root fees are paid from the balance
+        Some(root) if root_is_internal => root.fees.unwrap_or_default() + required[0],
+        Some(_) => required[0],
+        None => 0,
+    }
+}
+
+fn get_message_value(message: &ton_block::Message) -> (u128, u128) {
+    match message.int_header() {
+        Some(header) => (header.value.grams.0, header.fwd_fee.0),
+        None => (0, 0),
+    }
+}
+
+#[derive(Default)]
+struct TransactionFees {
+    storage_fee: u128,
+    gas_fee: u128,
+    gas_used: u64,
+    fwd_fee: u128,
+    action_fee: u128,
+    total_fees: u128,
+}
+
+impl TransactionFees {
+    fn compute(tx: &ton_block::Transaction) -> Result<Self, JsValue> {
+        let description = match tx.read_description().handle_error()? {
+            ton_block::TransactionDescr::Ordinary(description) => description,
+            _ => return Err(ExecutorError::UnexpectedTransactionKind).handle_error(),
+        };
+
+        let mut fees = Self {
+            total_fees: tx.total_fees().grams.0,
+            ..Default::default()
+        };
+        if let Some(storage_ph) = &description.storage_ph {
+            fees.storage_fee = storage_ph.storage_fees_collected.0;
+        }
+        if let ton_block::TrComputePhase::Vm(compute_ph) = &description.compute_ph {
+            fees.gas_fee = compute_ph.gas_fees.0;
+            fees.gas_used = compute_ph.gas_used.0;
+        }
+        if let Some(action_ph) = &description.action {
+            fees.fwd_fee = action_ph
+                .total_fwd_fees
+                .as_ref()
+                .map(|x| x.0)
+                .unwrap_or_default();
+            fees.action_fee = action_ph
+                .total_action_fees
+                .as_ref()
+                .map(|x| x.0)
+                .unwrap_or_default();
+        }
+        Ok(fees)
+    }
+
+    fn append(&mut self, other: &Self) {
+        self.storage_fee += other.storage_fee;
+        self.gas_fee += other.gas_fee;
+        self.gas_used += other.gas_used;
+        self.fwd_fee += other.fwd_fee;
+        self.action_fee += other.action_fee;
+        self.total_fees += other.total_fees;
+    }
+
+    fn make_js(&self) -> JsValue {
+        ObjectBuilder::new()
+            .set("storageFee", self.storage_fee.to_string())
+            .set("gasFee", self.gas_fee.to_string())
+            .set("gasUsed", self.gas_used.to_string())
+            .set("fwdFee", self.fwd_fee.to_string())
+            .set("actionFee", self.action_fee.to_string())
+            .set("totalFees", self.total_fees.to_string())
+            .build()
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const ESTIMATED_FEES: &str = r#"
+export type EstimateFeesOptions = EmulateMessageTreeOptions & {
+    includeChain?: boolean,
+};
+
+export type TransactionFees = {
+    storageFee: string,
+    gasFee: string,
+    gasUsed: string,
+    fwdFee: string,
+    actionFee: string,
+    totalFees: string,
+};
+
+/**
+ * Fees of the root transaction and, with `includeChain`, of the whole message tree.
+ *
+ * `recommendedValue` is the inbound value the root transaction needs to cover
+ * attached values, forwarding fees and fees of every descendant transaction.
+ */
+export type EstimatedFees =
+    | { exitCode: number, resultArg?: number, computePhase: RejectedComputePhase }
+    | {
+        root: TransactionFees,
+        chain?: TransactionFees,
+        transactionCount?: number,
+        recommendedValue?: string,
+        complete?: boolean,
+    };
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "EstimateFeesOptions")]
+    pub type EstimateFeesOptions;
+
+    #[wasm_bindgen(typescript_type = "string | Uint8Array | SignedMessage")]
+    pub type EstimatedMessage;
+
+    #[wasm_bindgen(typescript_type = "Promise<EstimatedFees>")]
+    pub type PromiseEstimatedFees;
+}
+
+pub enum MessageTreeOutput {
+    Rejected {
+        exit_code: i32,
+        result_arg: Option<i32>,
+    },
+    Executed {
+        transactions: Vec<TreeTransaction>,
+        failed_messages: Vec<FailedTreeMessage>,
+        pending_messages: Vec<PendingTreeMessage>,
+    },
+}
+
+pub struct TreeTransaction {
+    pub transaction: ton_block::Transaction,
+    pub depth: u32,
+    pub parent: Option<u32>,
+}
+
+pub struct FailedTreeMessage {
+    pub message: ton_block::Message,
+    pub dst: ton_block::MsgAddressInt,
+    pub error: String,
+    pub depth: u32,
+    pub parent: Option<u32>,
+}
+
+pub struct PendingTreeMessage {
+    pub message: ton_block::Message,
+    pub dst: ton_block::MsgAddressInt,
+    pub depth: u32,
+    pub parent: Option<u32>,
+}
+
+fn get_last_trans_lt(account: &ton_types::Cell) -> Result<u64, JsValue> {
+    let account = ton_block::Account::construct_from_cell(account.clone()).handle_error()?;
+    Ok(account.last_tr_time().unwrap_or_default())
+}
+
+const DEFAULT_MAX_DEPTH: u32 = 16;
+const DEFAULT_MAX_TRANSACTIONS: u32 = 128;
+
+fn parse_accounts_map(
+    accounts: Option<AccountsMap>,
+) -> Result<HashMap<ton_block::MsgAddressInt, ton_types::Cell>, JsValue> {
+    let mut result = HashMap::new();
+
+    let accounts = match accounts {
+        Some(accounts) => match accounts.dyn_into::<js_sys::Map>() {
+            Ok(accounts) => accounts,
+            Err(_) => return Err(ExecutorError::MapExpected).handle_error(),
+        },
+        None => return Ok(result),
+    };
+
+    for entry in accounts.entries() {
+        let entry: js_sys::Array = entry?.unchecked_into();
+        let address = match entry.get(0).as_string() {
+            Some(address) => parse_address(&address)?,
+            None => return Err(TokensJsonError::StringExpected).handle_error(),
+        };
+        let account = parse_cell(entry.get(1).unchecked_ref::<Boc>())?;
+        result.insert(address, account);
+    }
+
+    Ok(result)
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const EMULATED_MESSAGE_TREE: &str = r#"
+export type EmulateMessageTreeOptions = {
+    maxDepth?: number,
+    maxTransactions?: number,
+    disableSignatureCheck?: boolean,
+    /**
+     * Block LT for every emulated transaction,
+     * the latest LT of the involved accounts by default
+     */
+    blockLt?: string | number,
+    fetchAccount?: (address: string) =>
+        | string
+        | Uint8Array
+        | undefined
+        | Promise<string | Uint8Array | undefined>,
+};
+
+export type MessageTreeTransaction = {
+    transaction: Transaction,
+    computePhase: ComputePhase,
+    actionPhase?: ActionPhase,
+    bouncePhase?: BouncePhase,
+    depth: number,
+    parent?: number,
+};
+
+export type FailedMessage = {
+    dst: string,
+    boc: string,
+    error: string,
+    depth: number,
+    parent?: number,
+};
+
+export type PendingMessage = {
+    dst: string,
+    boc: string,
+    depth: number,
+    parent?: number,
+};
+
+export type EmulatedMessageTree =
+    | { exitCode: number, resultArg?: number, computePhase: RejectedComputePhase }
+    | {
+        transactions: MessageTreeTransaction[],
+        accounts: Map<string, string>,
+        failedMessages: FailedMessage[],
+        pendingMessages: PendingMessage[],
+    };
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Map<string, string | Uint8Array>")]
+    pub type AccountsMap;
+
+    #[wasm_bindgen(typescript_type = "EmulateMessageTreeOptions")]
+    pub type EmulateMessageTreeOptions;
+
+    #[wasm_bindgen(typescript_type = "Promise<EmulatedMessageTree>")]
+    pub type PromiseEmulatedMessageTree;
+}
+
+fn parse_result_arg(arg: Option<&ton_vm::stack::StackItem>) -> Option<i32> {
+    arg.and_then(|arg| arg.as_integer().ok())
+        .and_then(|arg| arg.into(i32::MIN..=i32::MAX).ok())
+}
+
+/// Rejected external messages produce no transaction, so only the exit code
+/// and its argument are known. Gas counters are not reported in this case
+fn make_no_accept_output(code: i32, result_arg: Option<i32>) -> ObjectBuilder {
+    let compute_phase = ObjectBuilder::new()
+        .set("type", "vm")
+        .set("success", false)
+        .set("exitCode", code)
+        .set("exitArg", result_arg)
+        .build();
+
+    ObjectBuilder::new()
+        .set("exitCode", code)
+        .set("resultArg", result_arg)
+        .set("computePhase", compute_phase)
+}
+
+fn make_executed_transaction(tx: ton_block::Transaction) -> Result<ObjectBuilder, JsValue> {
+    let description = match tx.read_description().handle_error()? {
+        ton_block::TransactionDescr::Ordinary(description) => description,
+        _ => return Err(ExecutorError::UnexpectedTransactionKind).handle_error(),
+    };
+
+    let hash = tx.hash().handle_error()?;
+    let tx = nt::core::models::Transaction::try_from((hash, tx)).handle_error()?;
+
+    Ok(ObjectBuilder::new()
+        .set("transaction", make_transaction(tx))
+        .set("computePhase", make_compute_phase(&description.compute_ph))
+        .set(
+            "actionPhase",
+            description.action.as_ref().map(make_action_phase),
+        )
+        .set(
+            "bouncePhase",
+            description.bounce.as_ref().map(make_bounce_phase),
+        ))
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const EXECUTE_LOCAL_OPTIONS: &str = r#"
+export type ExecuteLocalOptions = {
+    blockLt?: string | number,
+    lastTransLt?: string | number,
+    seedBlock?: string,
+    libraries?: string | Uint8Array,
+    overrideBalance?: string | number,
+    debug?: boolean,
+    trace?: boolean,
+};
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ExecuteLocalOptions")]
+    pub type ExecuteLocalOptions;
+}
+
+#[derive(Default)]
+struct ParsedExecuteLocalOptions {
+    block_lt: Option<u64>,
+    last_trans_lt: Option<u64>,
+    seed_block: Option<ton_types::UInt256>,
+    libraries: Option<ton_types::Cell>,
+    override_balance: Option<ton_block::Grams>,
+    debug: bool,
+    trace: bool,
+}
+
+fn parse_execute_local_options(
+    options: Option<ExecuteLocalOptions>,
+) -> Result<ParsedExecuteLocalOptions, JsValue> {
+    let options: JsValue = match options {
+        Some(options) if options.is_object() => options.unchecked_into(),
+        Some(_) => return Err(TokensJsonError::ObjectExpected).handle_error(),
+        None => return Ok(Default::default()),
+    };
+
+    let seed_block = match get_optional_field(&options, "seedBlock")? {
+        Some(value) => match value.as_string() {
+            Some(value) => Some(parse_hash(&value)?),
+            None => return Err(TokensJsonError::StringExpected).handle_error(),
+        },
+        None => None,
+    };
+
+    let libraries = match get_optional_field(&options, "libraries")? {
+        Some(value) => Some(parse_cell(value.unchecked_ref::<Boc>())?),
+        None => None,
+    };
+
+    let override_balance = match get_optional_field(&options, "overrideBalance")? {
+        Some(value) => {
+            match parse_token_value(&ton_abi::ParamType::Token, value)
+                .map_err(|e| e.with_field("overrideBalance"))?
+            {
+                ton_abi::TokenValue::Token(grams) => Some(grams),
+                _ => return Err(TokensJsonError::NumberExpected).handle_error(),
+            }
+        }
+        None => None,
+    };
+
+    let debug = parse_optional_bool(&options, "debug")?;
+    let trace = parse_optional_bool(&options, "trace")?;
+
+    Ok(ParsedExecuteLocalOptions {
+        block_lt: parse_optional_u64(&options, "blockLt")?,
+        last_trans_lt: parse_optional_u64(&options, "lastTransLt")?,
+        seed_block,
+        libraries,
+        override_balance,
+        debug,
+        trace,
+    })
+}
+
+fn parse_optional_bool(object: &JsValue, key: &str) -> Result<bool, JsValue> {
+    match get_optional_field(object, key)? {
+        Some(value) => match value.as_bool() {
+            Some(value) => Ok(value),
+            None => Err(TokensJsonError::BoolExpected).handle_error(),
+        },
+        None => Ok(false),
+    }
+}
+
+fn parse_optional_u64(object: &JsValue, key: &str) -> Result<Option<u64>, JsValue> {
+    let value = match get_optional_field(object, key)? {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+
+    match parse_token_value(&ton_abi::ParamType::Uint(64), value).map_err(|e| e.with_field(key))? {
+        ton_abi::TokenValue::Uint(value) => match value.number.to_u64() {
+            Some(value) => Ok(Some(value)),
+            None => Err(TokensJsonError::InvalidNumber(key.to_owned())).handle_error(),
+        },
+        _ => Err(TokensJsonError::NumberExpected).handle_error(),
+    }
+}
+
+fn get_optional_field(object: &JsValue, key: &str) -> Result<Option<JsValue>, JsValue> {
+    let value = js_sys::Reflect::get(object, &JsValue::from_str(key))?;
+    Ok(if value.is_null() || value.is_undefined() {
+        None
+    } else {
+        Some(value)
+    })
+}
+
+#[derive(thiserror::Error, Debug)]
+enum ExecutorError {
+    #[error("Unexpected transaction kind")]
+    UnexpectedTransactionKind,
+    #[error("Expected Map")]
+    MapExpected,
+    #[error("Expected function")]
+    FunctionExpected,
+    #[error("No transactions produced")]
+    NoTransactionsProduced,
+    #[error("Can't override balance of an empty account")]
+    BalanceOverrideForEmptyAccount,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(parent: Option<usize>, value: u128, fwd_fee: u128, fees: Option<u128>) -> FeeNode {
+        FeeNode {
+            parent,
+            value,
+            fwd_fee,
+            fees,
+        }
+    }
+
+    #[test]
+    fn recommended_value_covers_root_fees() {
+        let nodes = [node(None, 1000, 1, Some(10))];
+        assert_eq!(compute_recommended_value(&nodes, true), 10);
+        assert_eq!(compute_recommended_value(&nodes, false), 0);
+    }
+
+    #[test]
+    fn recommended_value_includes_attached_values_and_forwarding() {
+        let nodes = [
+            node(None, 0, 0, Some(10)),
+            node(Some(0), 100, 5, Some(20)),
+            node(Some(0), 7, 2, Some(1)),
+        ];
+        assert_eq!(compute_recommended_value(&nodes, true), 10 + 105 + 9);
+    }
+
+    #[test]
+    fn recommended_value_covers_underfunded_descendants() {
+        let nodes = [
+            node(None, 0, 0, Some(10)),
+            // Attached value is not enough to pay the fees and the next hop
+            node(Some(0), 10, 5, Some(20)),
+            // Not executed
+            node(Some(1), 50, 3, None),
+        ];
+        assert_eq!(compute_recommended_value(&nodes, true), 10 + 5 + (20 + 53));
+        assert_eq!(compute_recommended_value(&nodes, false), 5 + (20 + 53));
+    }
+
+    #[test]
+    fn chain_fees_are_summed() {
+        let mut chain = TransactionFees::default();
+        for total_fees in [10, 20, 30] {
+            chain.append(&TransactionFees {
+                storage_fee: 1,
+                gas_fee: total_fees - 2,
+                gas_used: 100,
+                fwd_fee: 1,
+                action_fee: 1,
+                total_fees,
+            });
+        }
+        assert_eq!(chain.storage_fee, 3);
+        assert_eq!(chain.gas_fee, 54);
+        assert_eq!(chain.gas_used, 300);
+        assert_eq!(chain.fwd_fee, 3);
+        assert_eq!(chain.action_fee, 3);
+        assert_eq!(chain.total_fees, 60);
+    }
+}
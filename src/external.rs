@@ -162,3 +162,127 @@ impl nt::external::JrpcConnection for JrpcConnector {
         Ok(rx.await.unwrap_or(Err(JrpcError::RequestFailed))?)
     }
 }
+
+pub struct StorageImpl {
+    inner: Arc<IStorage>,
+}
+
+impl StorageImpl {
+    pub fn new(storage: IStorage) -> Self {
+        Self {
+            inner: Arc::new(storage),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl nt::external::Storage for StorageImpl {
+    async fn get(&self, key: &str) -> Result<Option<String>> {
+        let (tx, rx) = oneshot::channel();
+        self.inner.get(key, StorageQueryResultHandler { tx });
+        Ok(rx.await.unwrap_or(Err(StorageError::QueryDropped))?)
+    }
+
+    async fn set(&self, key: &str, value: &str) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.inner.set(key, value, StorageQueryHandler { tx });
+        Ok(rx.await.unwrap_or(Err(StorageError::QueryDropped))?)
+    }
+
+    fn set_unchecked(&self, key: &str, value: &str) {
+        self.inner.set_unchecked(key, value);
+    }
+
+    async fn remove(&self, key: &str) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.inner.remove(key, StorageQueryHandler { tx });
+        Ok(rx.await.unwrap_or(Err(StorageError::QueryDropped))?)
+    }
+
+    fn remove_unchecked(&self, key: &str) {
+        self.inner.remove_unchecked(key);
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const STORAGE: &str = r#"
+export interface IStorage {
+  get(key: string, handler: StorageQueryResultHandler): void;
+  set(key: string, value: string, handler: StorageQueryHandler): void;
+  setUnchecked(key: string, value: string): void;
+  remove(key: string, handler: StorageQueryHandler): void;
+  removeUnchecked(key: string): void;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "IStorage")]
+    pub type IStorage;
+
+    #[wasm_bindgen(method)]
+    pub fn get(this: &IStorage, key: &str, handler: StorageQueryResultHandler);
+
+    #[wasm_bindgen(method)]
+    pub fn set(this: &IStorage, key: &str, value: &str, handler: StorageQueryHandler);
+
+    #[wasm_bindgen(method, js_name = "setUnchecked")]
+    pub fn set_unchecked(this: &IStorage, key: &str, value: &str);
+
+    #[wasm_bindgen(method)]
+    pub fn remove(this: &IStorage, key: &str, handler: StorageQueryHandler);
+
+    #[wasm_bindgen(method, js_name = "removeUnchecked")]
+    pub fn remove_unchecked(this: &IStorage, key: &str);
+}
+
+unsafe impl Send for IStorage {}
+unsafe impl Sync for IStorage {}
+
+#[wasm_bindgen]
+pub struct StorageQueryResultHandler {
+    #[wasm_bindgen(skip)]
+    pub tx: oneshot::Sender<StorageQueryResult<Option<String>>>,
+}
+
+#[wasm_bindgen]
+impl StorageQueryResultHandler {
+    #[wasm_bindgen(js_name = "onResult")]
+    pub fn on_result(self, data: Option<String>) {
+        let _ = self.tx.send(Ok(data));
+    }
+
+    #[wasm_bindgen(js_name = "onError")]
+    pub fn on_error(self, _: JsValue) {
+        let _ = self.tx.send(Err(StorageError::QueryFailed));
+    }
+}
+
+#[wasm_bindgen]
+pub struct StorageQueryHandler {
+    #[wasm_bindgen(skip)]
+    pub tx: oneshot::Sender<StorageQueryResult<()>>,
+}
+
+#[wasm_bindgen]
+impl StorageQueryHandler {
+    #[wasm_bindgen(js_name = "onResult")]
+    pub fn on_result(self) {
+        let _ = self.tx.send(Ok(()));
+    }
+
+    #[wasm_bindgen(js_name = "onError")]
+    pub fn on_error(self, _: JsValue) {
+        let _ = self.tx.send(Err(StorageError::QueryFailed));
+    }
+}
+
+type StorageQueryResult<T> = Result<T, StorageError>;
+
+#[derive(thiserror::Error, Debug)]
+pub enum StorageError {
+    #[error("Query dropped unexpectedly")]
+    QueryDropped,
+    #[error("Query failed")]
+    QueryFailed,
+}
@@ -2,11 +2,12 @@ use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
 use gloo_utils::format::JsValueSerdeExt;
-use nt::utils::TrustMe;
+use nt::utils::{Clock, TrustMe};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::*;
 
+use crate::executor::*;
 use crate::models::*;
 use crate::transport::*;
 use crate::utils::*;
@@ -21,12 +22,14 @@ pub struct GenericContract {
 
 impl GenericContract {
     pub fn new(
+        clock: Arc<nt::utils::ClockWithOffset>,
         transport: TransportHandle,
         contract: nt::core::generic_contract::GenericContract,
     ) -> Self {
         Self {
             address: contract.address().to_string(),
             inner: Arc::new(GenericContractState {
+                clock,
                 transport,
                 contract: Mutex::new(contract),
             }),
@@ -64,6 +67,38 @@ impl GenericContract {
         })))
     }
 
+    #[wasm_bindgen(js_name = "estimateFees")]
+    pub fn estimate_fees(
+        &self,
+        message: SignedMessage,
+        options: Option<EstimateFeesOptions>,
+    ) -> Result<PromiseEstimatedFees, JsValue> {
+        let inner = self.inner.clone();
+        let message = parse_signed_message(message)?;
+        let include_chain = parse_include_chain(options.as_ref())?;
+
+        Ok(JsCast::unchecked_into(future_to_promise(async move {
+            let config = inner
+                .transport
+                .as_ref()
+                .get_blockchain_config(inner.clock.as_ref(), false)
+                .await
+                .handle_error()?;
+            let utime = inner.clock.now_sec_u64() as u32;
+
+            MessageTreeEmulator::with_config(
+                config,
+                Default::default(),
+                message.message,
+                utime,
+                options.map(JsCast::unchecked_into),
+            )?
+            .with_transport(inner.transport.clone())
+            .estimate_fees(include_chain)
+            .await
+        })))
+    }
+
     #[wasm_bindgen(js_name = "sendMessage")]
     pub fn send_message(
         &self,
@@ -109,7 +144,10 @@ impl GenericContract {
             let block = inner.transport.get_block(&block_id).await?;
 
             let mut contract = inner.contract.lock().trust_me();
-            contract.handle_block(&block).await.handle_error()?;
+            match block {
+                Some(block) => contract.handle_block(&block).await.handle_error()?,
+                None => contract.refresh().await.handle_error()?,
+            }
 
             Ok(JsValue::undefined())
         }))
@@ -141,6 +179,7 @@ impl GenericContract {
 }
 
 pub struct GenericContractState {
+    clock: Arc<nt::utils::ClockWithOffset>,
     transport: TransportHandle,
     contract: Mutex<nt::core::generic_contract::GenericContract>,
 }
@@ -0,0 +1,382 @@
+use std::sync::Arc;
+
+use nt::crypto::{
+    EncryptedKeyCreateInput, EncryptedKeyPassword, EncryptedKeySigner, EncryptedKeyUpdateParams,
+};
+use secstr::SecUtf8;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::*;
+
+use crate::crypto::parse_mnemonic_type;
+use crate::external::{IStorage, StorageImpl};
+use crate::models::*;
+use crate::tokens_object::*;
+use crate::utils::*;
+
+const ENCRYPTED_KEY_SIGNER_NAME: &str = "encrypted_key";
+
+#[wasm_bindgen]
+pub struct KeyStore {
+    #[wasm_bindgen(skip)]
+    pub inner: Arc<nt::core::keystore::KeyStore>,
+}
+
+#[wasm_bindgen]
+impl KeyStore {
+    #[wasm_bindgen]
+    pub fn load(storage: IStorage) -> PromiseKeyStore {
+        let storage = Arc::new(StorageImpl::new(storage));
+
+        JsCast::unchecked_into(future_to_promise(async move {
+            let inner = nt::core::keystore::KeyStore::builder()
+                .with_signer(ENCRYPTED_KEY_SIGNER_NAME, EncryptedKeySigner::new())
+                .handle_error()?
+                .load(storage)
+                .await
+                .handle_error()?;
+
+            Ok(JsValue::from(KeyStore {
+                inner: Arc::new(inner),
+            }))
+        }))
+    }
+
+    #[wasm_bindgen(js_name = "getEntries")]
+    pub fn get_entries(&self) -> PromiseKeyStoreEntries {
+        let inner = self.inner.clone();
+
+        JsCast::unchecked_into(future_to_promise(async move {
+            Ok(inner
+                .get_entries()
+                .await
+                .into_iter()
+                .map(make_key_store_entry)
+                .collect::<js_sys::Array>()
+                .unchecked_into())
+        }))
+    }
+
+    #[wasm_bindgen(js_name = "addKey")]
+    pub fn add_key(&self, params: NewKeyParams) -> Result<PromiseKeyStoreEntry, JsValue> {
+        let inner = self.inner.clone();
+        let params: JsValue = params.into();
+        if !params.is_object() {
+            return Err(TokensJsonError::ObjectExpected).handle_error();
+        }
+
+        let name = js_sys::Reflect::get(&params, &JsValue::from_str("name"))?.as_string();
+        let phrase = match js_sys::Reflect::get(&params, &JsValue::from_str("phrase"))?.as_string()
+        {
+            Some(phrase) => SecUtf8::from(phrase.trim()),
+            None => return Err(TokensJsonError::StringExpected).handle_error(),
+        };
+        let mnemonic = js_sys::Reflect::get(&params, &JsValue::from_str("mnemonic"))?;
+        let mnemonic_type = parse_mnemonic_type(&mnemonic)?;
+        let password = js_sys::Reflect::get(&params, &JsValue::from_str("password"))?;
+        let password = parse_key_password(password)?;
+
+        Ok(JsCast::unchecked_into(future_to_promise(async move {
+            let entry = inner
+                .add_key::<EncryptedKeySigner>(EncryptedKeyCreateInput {
+                    name,
+                    phrase,
+                    mnemonic_type,
+                    password,
+                })
+                .await
+                .handle_error()?;
+            Ok(make_key_store_entry(entry))
+        })))
+    }
+
+    #[wasm_bindgen(js_name = "renameKey")]
+    pub fn rename_key(
+        &self,
+        public_key: &str,
+        name: String,
+    ) -> Result<PromiseKeyStoreEntry, JsValue> {
+        let inner = self.inner.clone();
+        let public_key = parse_public_key(public_key)?;
+
+        Ok(JsCast::unchecked_into(future_to_promise(async move {
+            let entry = inner
+                .update_key::<EncryptedKeySigner>(EncryptedKeyUpdateParams::Rename {
+                    public_key,
+                    name,
+                })
+                .await
+                .handle_error()?;
+            Ok(make_key_store_entry(entry))
+        })))
+    }
+
+    #[wasm_bindgen(js_name = "changePassword")]
+    pub fn change_password(
+        &self,
+        public_key: &str,
+        old_password: KeyPassword,
+        new_password: KeyPassword,
+    ) -> Result<PromiseKeyStoreEntry, JsValue> {
+        let inner = self.inner.clone();
+        let public_key = parse_public_key(public_key)?;
+        let old_password = parse_key_password(old_password.into())?;
+        let new_password = parse_key_password(new_password.into())?;
+
+        Ok(JsCast::unchecked_into(future_to_promise(async move {
+            let entry = inner
+                .update_key::<EncryptedKeySigner>(EncryptedKeyUpdateParams::ChangePassword {
+                    public_key,
+                    old_password,
+                    new_password,
+                })
+                .await
+                .handle_error()?;
+            Ok(make_key_store_entry(entry))
+        })))
+    }
+
+    #[wasm_bindgen(js_name = "exportKey")]
+    pub fn export_key(
+        &self,
+        public_key: &str,
+        password: KeyPassword,
+    ) -> Result<PromiseExportedKey, JsValue> {
+        let inner = self.inner.clone();
+        let public_key = parse_public_key(public_key)?;
+        let password = parse_key_password(password.into())?;
+
+        Ok(JsCast::unchecked_into(future_to_promise(async move {
+            let output = inner
+                .export_key::<EncryptedKeySigner>(EncryptedKeyPassword {
+                    public_key,
+                    password,
+                })
+                .await
+                .handle_error()?;
+
+            Ok(ObjectBuilder::new()
+                .set("phrase", output.phrase.unsecure())
+                .set("mnemonic", make_mnemonic_type(output.mnemonic_type))
+                .build())
+        })))
+    }
+
+    #[wasm_bindgen(js_name = "removeKey")]
+    pub fn remove_key(&self, public_key: &str) -> Result<PromiseOptionKeyStoreEntry, JsValue> {
+        let inner = self.inner.clone();
+        let public_key = parse_public_key(public_key)?;
+
+        Ok(JsCast::unchecked_into(future_to_promise(async move {
+            let entry = inner.remove_key(&public_key).await.handle_error()?;
+            Ok(entry
+                .map(make_key_store_entry)
+                .unwrap_or_else(JsValue::undefined))
+        })))
+    }
+
+    #[wasm_bindgen(js_name = "isPasswordCached")]
+    pub fn is_password_cached(&self, public_key: &str, duration: u32) -> Result<bool, JsValue> {
+        let public_key = parse_public_key(public_key)?;
+        Ok(self
+            .inner
+            .is_password_cached(public_key.as_bytes(), duration as u64))
+    }
+
+    #[wasm_bindgen(js_name = "signData")]
+    pub fn sign_data(
+        &self,
+        public_key: &str,
+        password: KeyPassword,
+        data: &str,
+        signature_id: Option<i32>,
+    ) -> Result<PromiseString, JsValue> {
+        let inner = self.inner.clone();
+        let public_key = parse_public_key(public_key)?;
+        let password = parse_key_password(password.into())?;
+        let data = parse_hex_or_base64_bytes(data).handle_error()?;
+
+        Ok(JsCast::unchecked_into(future_to_promise(async move {
+            let signature = inner
+                .sign::<EncryptedKeySigner>(
+                    &data,
+                    signature_id,
+                    EncryptedKeyPassword {
+                        public_key,
+                        password,
+                    },
+                )
+                .await
+                .handle_error()?;
+            Ok(JsValue::from(base64::encode(signature)))
+        })))
+    }
+
+    #[wasm_bindgen(js_name = "signMessage")]
+    pub fn sign_message(
+        &self,
+        message: &UnsignedMessage,
+        public_key: &str,
+        password: KeyPassword,
+        signature_id: Option<i32>,
+    ) -> Result<PromiseSignedMessage, JsValue> {
+        let inner = self.inner.clone();
+        let message = message.inner.clone();
+        let public_key = parse_public_key(public_key)?;
+        let password = parse_key_password(password.into())?;
+
+        Ok(JsCast::unchecked_into(future_to_promise(async move {
+            let signature = inner
+                .sign::<EncryptedKeySigner>(
+                    message.hash(),
+                    signature_id,
+                    EncryptedKeyPassword {
+                        public_key,
+                        password,
+                    },
+                )
+                .await
+                .handle_error()?;
+
+            message
+                .sign(&signature)
+                .handle_error()
+                .and_then(make_signed_message)
+                .map(JsValue::from)
+        })))
+    }
+}
+
+fn make_key_store_entry(entry: nt::core::keystore::KeyStoreEntry) -> JsValue {
+    ObjectBuilder::new()
+        .set("signerName", entry.signer_name)
+        .set("name", entry.name)
+        .set("publicKey", hex::encode(entry.public_key.as_bytes()))
+        .set("masterKey", hex::encode(entry.master_key.as_bytes()))
+        .set("accountId", entry.account_id)
+        .build()
+}
+
+fn make_mnemonic_type(mnemonic_type: nt::crypto::MnemonicType) -> JsValue {
+    match mnemonic_type {
+        nt::crypto::MnemonicType::Legacy => ObjectBuilder::new().set("type", "legacy").build(),
+        nt::crypto::MnemonicType::Labs(account_id) => ObjectBuilder::new()
+            .set("type", "bip39")
+            .set("accountId", account_id)
+            .build(),
+    }
+}
+
+fn parse_key_password(value: JsValue) -> Result<nt::crypto::Password, JsValue> {
+    if !value.is_object() {
+        return Err(TokensJsonError::ObjectExpected).handle_error();
+    }
+
+    let kind = js_sys::Reflect::get(&value, &JsValue::from_str("type"))?;
+    match kind.as_string().as_deref() {
+        Some("cached") => Ok(nt::crypto::Password::FromCache),
+        Some("explicit") => {
+            let password =
+                match js_sys::Reflect::get(&value, &JsValue::from_str("password"))?.as_string() {
+                    Some(password) => SecUtf8::from(password),
+                    None => return Err(TokensJsonError::StringExpected).handle_error(),
+                };
+
+            let cache_for = js_sys::Reflect::get(&value, &JsValue::from_str("cacheFor"))?;
+            let clear_cache = js_sys::Reflect::get(&value, &JsValue::from_str("clearCache"))?;
+
+            let cache_behavior = match cache_for.as_f64() {
+                Some(duration) if duration.fract() == 0.0 && duration >= 0.0 => {
+                    nt::crypto::PasswordCacheBehavior::Store(duration as u64)
+                }
+                Some(duration) => {
+                    return Err(TokensJsonError::InvalidNumber(duration.to_string())).handle_error()
+                }
+                None if !cache_for.is_undefined() && !cache_for.is_null() => {
+                    return Err(TokensJsonError::NumberExpected).handle_error()
+                }
+                None if clear_cache.as_bool().unwrap_or_default() => {
+                    nt::crypto::PasswordCacheBehavior::Remove
+                }
+                None => nt::crypto::PasswordCacheBehavior::Nop,
+            };
+
+            Ok(nt::crypto::Password::Explicit(
+                nt::crypto::PasswordExplicit {
+                    password,
+                    cache_behavior,
+                },
+            ))
+        }
+        _ => Err(KeyStoreError::InvalidPasswordType).handle_error(),
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const KEY_STORE: &str = r#"
+export type KeyStoreEntry = {
+    signerName: string,
+    name: string,
+    publicKey: string,
+    masterKey: string,
+    accountId: number,
+};
+
+export type KeyMnemonicType =
+    | { type: 'legacy' }
+    | { type: 'bip39', accountId?: number };
+
+export type KeyPassword =
+    | { type: 'cached' }
+    | {
+        type: 'explicit',
+        password: string,
+        cacheFor?: number,
+        clearCache?: boolean,
+    };
+
+export type NewKeyParams = {
+    name?: string,
+    phrase: string,
+    mnemonic: KeyMnemonicType,
+    password: KeyPassword,
+};
+
+export type ExportedKey = {
+    phrase: string,
+    mnemonic: KeyMnemonicType,
+};
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Promise<KeyStore>")]
+    pub type PromiseKeyStore;
+
+    #[wasm_bindgen(typescript_type = "Promise<KeyStoreEntry>")]
+    pub type PromiseKeyStoreEntry;
+
+    #[wasm_bindgen(typescript_type = "Promise<KeyStoreEntry | undefined>")]
+    pub type PromiseOptionKeyStoreEntry;
+
+    #[wasm_bindgen(typescript_type = "Promise<Array<KeyStoreEntry>>")]
+    pub type PromiseKeyStoreEntries;
+
+    #[wasm_bindgen(typescript_type = "Promise<ExportedKey>")]
+    pub type PromiseExportedKey;
+
+    #[wasm_bindgen(typescript_type = "Promise<SignedMessage>")]
+    pub type PromiseSignedMessage;
+
+    #[wasm_bindgen(typescript_type = "KeyPassword")]
+    pub type KeyPassword;
+
+    #[wasm_bindgen(typescript_type = "NewKeyParams")]
+    pub type NewKeyParams;
+}
+
+#[derive(thiserror::Error, Debug)]
+enum KeyStoreError {
+    #[error("Invalid password type")]
+    InvalidPasswordType,
+}
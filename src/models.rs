@@ -124,15 +124,21 @@ export type Message = {
     bounced: boolean,
     body?: string,
     bodyHash?: string,
+    comment?: string,
 };
 "#;
 
 pub fn make_message(data: models::Message) -> Message {
-    let (body, body_hash) = if let Some(body) = data.body {
+    let (body, body_hash, comment) = if let Some(body) = data.body {
+        let comment = crate::comment::parse_plain_comment(&body.data);
         let data = ton_types::serialize_toc(&body.data).expect("Shouldn't fail");
-        (Some(base64::encode(data)), Some(body.hash.to_hex_string()))
+        (
+            Some(base64::encode(data)),
+            Some(body.hash.to_hex_string()),
+            comment,
+        )
     } else {
-        (None, None)
+        (None, None, None)
     };
 
     ObjectBuilder::new()
@@ -144,6 +150,7 @@ pub fn make_message(data: models::Message) -> Message {
         .set("bounced", data.bounced)
         .set("body", body)
         .set("bodyHash", body_hash)
+        .set("comment", comment)
         .build()
         .unchecked_into()
 }
@@ -368,15 +375,143 @@ export type DecodedTransactionEvents = Array<DecodedEvent>;
 #[wasm_bindgen(typescript_custom_section)]
 const TRANSACTION_EXECUTOR_OUTPUT: &str = r#"
 export type TransactionExecutorOutput =
-    | { exitCode: number }
-    | { account: string, transaction: Transaction }
+    | {
+        exitCode: number,
+        resultArg?: number,
+        computePhase: RejectedComputePhase,
+        trace?: TvmTrace,
+    }
+    | {
+        account: string,
+        transaction: Transaction,
+        computePhase: ComputePhase,
+        actionPhase?: ActionPhase,
+        bouncePhase?: BouncePhase,
+        trace?: TvmTrace,
+    };
 "#;
 
+#[wasm_bindgen(typescript_custom_section)]
+const TRANSACTION_PHASES: &str = r#"
+export type ComputePhase =
+    | { type: 'skipped', reason: 'noState' | 'badState' | 'noGas' }
+    | {
+        type: 'vm',
+        success: boolean,
+        msgStateUsed: boolean,
+        accountActivated: boolean,
+        gasFees: string,
+        gasUsed: string,
+        gasLimit: string,
+        gasCredit?: string,
+        mode: number,
+        exitCode: number,
+        exitArg?: number,
+        vmSteps: number,
+    };
+
+export type RejectedComputePhase = {
+    type: 'vm',
+    success: false,
+    exitCode: number,
+    exitArg?: number,
+};
+
+export type ActionPhase = {
+    success: boolean,
+    valid: boolean,
+    noFunds: boolean,
+    totalFwdFees?: string,
+    totalActionFees?: string,
+    resultCode: number,
+    resultArg?: number,
+    totalActions: number,
+    specActions: number,
+    skippedActions: number,
+    messagesCreated: number,
+};
+
+export type BouncePhase =
+    | { type: 'negativeFunds' }
+    | { type: 'noFunds', requiredFwdFees: string }
+    | { type: 'ok', msgFees: string, fwdFees: string };
+"#;
+
+pub fn make_compute_phase(data: &ton_block::TrComputePhase) -> JsValue {
+    match data {
+        ton_block::TrComputePhase::Skipped(skipped) => ObjectBuilder::new()
+            .set("type", "skipped")
+            .set(
+                "reason",
+                match skipped.reason {
+                    ton_block::ComputeSkipReason::NoState => "noState",
+                    ton_block::ComputeSkipReason::BadState => "badState",
+                    ton_block::ComputeSkipReason::NoGas => "noGas",
+                },
+            )
+            .build(),
+        ton_block::TrComputePhase::Vm(vm) => ObjectBuilder::new()
+            .set("type", "vm")
+            .set("success", vm.success)
+            .set("msgStateUsed", vm.msg_state_used)
+            .set("accountActivated", vm.account_activated)
+            .set("gasFees", vm.gas_fees.0.to_string())
+            .set("gasUsed", vm.gas_used.0.to_string())
+            .set("gasLimit", vm.gas_limit.0.to_string())
+            .set("gasCredit", vm.gas_credit.as_ref().map(|x| x.0.to_string()))
+            .set("mode", vm.mode)
+            .set("exitCode", vm.exit_code)
+            .set("exitArg", vm.exit_arg)
+            .set("vmSteps", vm.vm_steps)
+            .build(),
+    }
+}
+
+pub fn make_action_phase(data: &ton_block::TrActionPhase) -> JsValue {
+    ObjectBuilder::new()
+        .set("success", data.success)
+        .set("valid", data.valid)
+        .set("noFunds", data.no_funds)
+        .set(
+            "totalFwdFees",
+            data.total_fwd_fees.as_ref().map(|x| x.0.to_string()),
+        )
+        .set(
+            "totalActionFees",
+            data.total_action_fees.as_ref().map(|x| x.0.to_string()),
+        )
+        .set("resultCode", data.result_code)
+        .set("resultArg", data.result_arg)
+        .set("totalActions", data.tot_actions)
+        .set("specActions", data.spec_actions)
+        .set("skippedActions", data.skipped_actions)
+        .set("messagesCreated", data.msgs_created)
+        .build()
+}
+
+pub fn make_bounce_phase(data: &ton_block::TrBouncePhase) -> JsValue {
+    match data {
+        ton_block::TrBouncePhase::Negfunds => {
+            ObjectBuilder::new().set("type", "negativeFunds").build()
+        }
+        ton_block::TrBouncePhase::Nofunds(no_funds) => ObjectBuilder::new()
+            .set("type", "noFunds")
+            .set("requiredFwdFees", no_funds.req_fwd_fees.0.to_string())
+            .build(),
+        ton_block::TrBouncePhase::Ok(ok) => ObjectBuilder::new()
+            .set("type", "ok")
+            .set("msgFees", ok.msg_fees.0.to_string())
+            .set("fwdFees", ok.fwd_fees.0.to_string())
+            .build(),
+    }
+}
+
 #[wasm_bindgen(typescript_custom_section)]
 const EXECUTION_OUTPUT: &str = r#"
 export type ExecutionOutput = {
     output?: TokensObject,
     code: number,
+    trace?: TvmTrace,
 };
 "#;
 
@@ -0,0 +1,128 @@
+use nt::core::models::TokenWalletVersion;
+use nt::core::ton_wallet::WalletType;
+use ton_block::Deserializable;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::models::*;
+use crate::token_wallet::*;
+use crate::ton_wallet::*;
+use crate::utils::*;
+
+#[wasm_bindgen(js_name = "parseKnownTransaction")]
+pub fn parse_known_transaction(
+    transaction: &Boc,
+    hints: Option<KnownTransactionHints>,
+) -> Result<Option<KnownTransaction>, JsValue> {
+    let (wallet_type, token_wallet_versions) = parse_known_transaction_hints(hints)?;
+
+    let cell = parse_cell(transaction)?;
+    let hash = cell.repr_hash();
+    let raw = ton_block::Transaction::construct_from_cell(cell).handle_error()?;
+
+    let info = wallet_type.and_then(|wallet_type| {
+        nt::core::parsing::parse_transaction_additional_info(&raw, wallet_type)
+    });
+
+    let token_info = match raw.description.read_struct().handle_error()? {
+        ton_block::TransactionDescr::Ordinary(description) => {
+            token_wallet_versions.into_iter().find_map(|version| {
+                nt::core::parsing::parse_token_transaction(&raw, &description, version)
+            })
+        }
+        _ => None,
+    };
+
+    if info.is_none() && token_info.is_none() {
+        return Ok(None);
+    }
+
+    let transaction = nt::core::models::Transaction::try_from((hash, raw)).handle_error()?;
+    Ok(Some(
+        ObjectBuilder::new()
+            .set("transaction", make_transaction(transaction))
+            .set(
+                "info",
+                info.map(make_transaction_additional_info).transpose()?,
+            )
+            .set(
+                "tokenInfo",
+                token_info
+                    .map(make_token_wallet_transaction_info)
+                    .transpose()?,
+            )
+            .build()
+            .unchecked_into(),
+    ))
+}
+
+type ParsedKnownTransactionHints = (Option<WalletType>, Vec<TokenWalletVersion>);
+
+fn parse_known_transaction_hints(
+    hints: Option<KnownTransactionHints>,
+) -> Result<ParsedKnownTransactionHints, JsValue> {
+    let hints: JsValue = match hints {
+        Some(hints) => hints.into(),
+        None => JsValue::undefined(),
+    };
+    if !hints.is_undefined() && !hints.is_null() && !hints.is_object() {
+        return Err(TokensJsonError::ObjectExpected).handle_error();
+    }
+
+    let get_field = |key: &str| -> Result<Option<JsValue>, JsValue> {
+        if !hints.is_object() {
+            return Ok(None);
+        }
+        let value = js_sys::Reflect::get(&hints, &JsValue::from_str(key))?;
+        Ok(if value.is_null() || value.is_undefined() {
+            None
+        } else {
+            Some(value)
+        })
+    };
+
+    // NOTE: wallet parsers don't check the contract code,
+    // so the wallet type must be known beforehand
+    let wallet_type = match (get_field("walletType")?, get_field("account")?) {
+        (Some(wallet_type), _) => Some(parse_wallet_type(wallet_type.unchecked_into())?),
+        (None, Some(account)) => {
+            guess_account_wallet_type(&parse_account_stuff(account.unchecked_ref::<Boc>())?)
+        }
+        (None, None) => None,
+    };
+
+    let token_wallet_versions = match get_field("tokenWalletVersion")? {
+        Some(version) => vec![parse_token_wallet_version(version.unchecked_into())?],
+        None => vec![TokenWalletVersion::Tip3, TokenWalletVersion::OldTip3v4],
+    };
+
+    Ok((wallet_type, token_wallet_versions))
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const KNOWN_TRANSACTION: &str = r#"
+/**
+ * Wallet transactions are parsed only if the wallet type is specified
+ * or can be detected from the code of the `account`
+ */
+export type KnownTransactionHints = {
+    walletType?: WalletType,
+    account?: string | Uint8Array,
+    tokenWalletVersion?: TokenWalletVersion,
+};
+
+export type KnownTransaction = {
+    transaction: Transaction,
+    info?: TransactionAdditionalInfo,
+    tokenInfo?: TokenWalletTransactionInfo,
+};
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "KnownTransactionHints")]
+    pub type KnownTransactionHints;
+
+    #[wasm_bindgen(typescript_type = "KnownTransaction")]
+    pub type KnownTransaction;
+}
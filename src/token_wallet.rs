@@ -0,0 +1,478 @@
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use nt::utils::TrustMe;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::*;
+
+use crate::models::*;
+use crate::transport::TransportHandle;
+use crate::utils::*;
+
+#[wasm_bindgen]
+pub struct TokenWallet {
+    #[wasm_bindgen(skip)]
+    pub address: String,
+    #[wasm_bindgen(skip)]
+    pub owner: String,
+    #[wasm_bindgen(skip)]
+    pub root_token_contract: String,
+    #[wasm_bindgen(skip)]
+    pub inner: Arc<TokenWalletState>,
+}
+
+impl TokenWallet {
+    pub fn new(transport: TransportHandle, wallet: nt::core::token_wallet::TokenWallet) -> Self {
+        Self {
+            address: wallet.address().to_string(),
+            owner: wallet.owner().to_string(),
+            root_token_contract: wallet.symbol().root_token_contract.to_string(),
+            inner: Arc::new(TokenWalletState {
+                transport,
+                wallet: Mutex::new(wallet),
+            }),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl TokenWallet {
+    #[wasm_bindgen(getter, js_name = "address")]
+    pub fn address(&self) -> String {
+        self.address.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = "owner")]
+    pub fn owner(&self) -> String {
+        self.owner.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = "rootTokenContract")]
+    pub fn root_token_contract(&self) -> String {
+        self.root_token_contract.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = "version")]
+    pub fn version(&self) -> TokenWalletVersion {
+        make_token_wallet_version(self.inner.wallet.lock().trust_me().version())
+    }
+
+    #[wasm_bindgen(getter, js_name = "symbol")]
+    pub fn symbol(&self) -> TokenSymbol {
+        make_token_symbol(self.inner.wallet.lock().trust_me().symbol())
+    }
+
+    #[wasm_bindgen(getter, js_name = "balance")]
+    pub fn balance(&self) -> String {
+        self.inner.wallet.lock().trust_me().balance().to_string()
+    }
+
+    #[wasm_bindgen(js_name = "contractState")]
+    pub fn contract_state(&self) -> ContractState {
+        make_contract_state(*self.inner.wallet.lock().trust_me().contract_state())
+    }
+
+    #[wasm_bindgen(js_name = "prepareTransfer")]
+    pub fn prepare_transfer(
+        &self,
+        destination: &str,
+        amount: &str,
+        notify_receiver: bool,
+        payload: Option<Boc>,
+    ) -> Result<InternalMessage, JsValue> {
+        let destination = parse_address(destination)?;
+        let amount = num_bigint::BigUint::from_str(amount.trim())
+            .map_err(|_| "Invalid amount")
+            .handle_error()?;
+        let payload = match payload {
+            Some(payload) => parse_cell(&payload)?,
+            None => Default::default(),
+        };
+
+        let wallet = self.inner.wallet.lock().trust_me();
+        let remaining_gas_to = wallet.owner().clone();
+
+        let message = wallet
+            .prepare_transfer(
+                destination,
+                amount,
+                notify_receiver,
+                payload,
+                remaining_gas_to,
+            )
+            .handle_error()?;
+
+        make_internal_message(message)
+    }
+
+    #[wasm_bindgen(js_name = "refresh")]
+    pub fn refresh(&mut self) -> PromiseVoid {
+        let inner = self.inner.clone();
+
+        // NOTE: method must be called through the external mutex
+        #[allow(clippy::await_holding_lock)]
+        JsCast::unchecked_into(future_to_promise(async move {
+            let mut wallet = inner.wallet.lock().trust_me();
+
+            wallet.refresh().await.handle_error()?;
+            Ok(JsValue::undefined())
+        }))
+    }
+
+    #[wasm_bindgen(js_name = "handleBlock")]
+    pub fn handle_block(&mut self, block_id: String) -> PromiseVoid {
+        let inner = self.inner.clone();
+
+        // NOTE: method must be called through the external mutex
+        #[allow(clippy::await_holding_lock)]
+        JsCast::unchecked_into(future_to_promise(async move {
+            let block = inner.transport.get_block(&block_id).await?;
+
+            let mut wallet = inner.wallet.lock().trust_me();
+            match block {
+                Some(block) => wallet.handle_block(&block).await.handle_error()?,
+                None => wallet.refresh().await.handle_error()?,
+            }
+
+            Ok(JsValue::undefined())
+        }))
+    }
+
+    #[wasm_bindgen(js_name = "preloadTransactions")]
+    pub fn preload_transactions(&mut self, lt: &str) -> Result<PromiseVoid, JsValue> {
+        let from_lt = u64::from_str(lt).handle_error()?;
+
+        let inner = self.inner.clone();
+
+        // NOTE: method must be called through the external mutex
+        #[allow(clippy::await_holding_lock)]
+        Ok(JsCast::unchecked_into(future_to_promise(async move {
+            let mut wallet = inner.wallet.lock().trust_me();
+
+            wallet.preload_transactions(from_lt).await.handle_error()?;
+            Ok(JsValue::undefined())
+        })))
+    }
+
+    #[wasm_bindgen(getter, js_name = "pollingMethod")]
+    pub fn polling_method(&self) -> PollingMethod {
+        make_polling_method(
+            self.inner
+                .wallet
+                .lock()
+                .trust_me()
+                .contract_subscription()
+                .polling_method(),
+        )
+    }
+}
+
+pub struct TokenWalletState {
+    transport: TransportHandle,
+    wallet: Mutex<nt::core::token_wallet::TokenWallet>,
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = "TokenWalletSubscriptionHandler")]
+    pub type TokenWalletSubscriptionHandlerImpl;
+
+    #[wasm_bindgen(method, js_name = "onBalanceChanged")]
+    pub fn on_balance_changed(this: &TokenWalletSubscriptionHandlerImpl, balance: String);
+
+    #[wasm_bindgen(method, js_name = "onTransactionsFound")]
+    pub fn on_transactions_found(
+        this: &TokenWalletSubscriptionHandlerImpl,
+        transactions: TokenWalletTransactionsList,
+        batch_info: TransactionsBatchInfo,
+    );
+}
+
+unsafe impl Send for TokenWalletSubscriptionHandlerImpl {}
+unsafe impl Sync for TokenWalletSubscriptionHandlerImpl {}
+
+pub struct TokenWalletSubscriptionHandler {
+    inner: TokenWalletSubscriptionHandlerImpl,
+}
+
+impl From<TokenWalletSubscriptionHandlerImpl> for TokenWalletSubscriptionHandler {
+    fn from(inner: TokenWalletSubscriptionHandlerImpl) -> Self {
+        Self { inner }
+    }
+}
+
+impl nt::core::token_wallet::TokenWalletSubscriptionHandler for TokenWalletSubscriptionHandler {
+    fn on_balance_changed(&self, balance: num_bigint::BigUint) {
+        self.inner.on_balance_changed(balance.to_string());
+    }
+
+    fn on_transactions_found(
+        &self,
+        transactions: Vec<
+            nt::core::models::TransactionWithData<nt::core::models::TokenWalletTransaction>,
+        >,
+        batch_info: nt::core::models::TransactionsBatchInfo,
+    ) {
+        self.inner.on_transactions_found(
+            transactions
+                .into_iter()
+                .map(make_token_wallet_transaction)
+                .collect::<js_sys::Array>()
+                .unchecked_into(),
+            make_transactions_batch_info(batch_info),
+        );
+    }
+}
+
+#[wasm_bindgen(js_name = "getTokenRootDetails")]
+pub fn get_token_root_details(
+    clock: &ClockWithOffset,
+    root_account_stuff_boc: &Boc,
+) -> Result<RootTokenContractDetails, JsValue> {
+    let contract = make_existing_contract(parse_account_stuff(root_account_stuff_boc)?);
+    let details = nt::core::token_wallet::RootTokenContractState(&contract)
+        .guess_details(clock.inner.as_ref())
+        .handle_error()?;
+    Ok(make_root_token_contract_details(details))
+}
+
+#[wasm_bindgen(js_name = "getTokenWalletAddress")]
+pub fn get_token_wallet_address(
+    clock: &ClockWithOffset,
+    root_account_stuff_boc: &Boc,
+    owner: &str,
+) -> Result<String, JsValue> {
+    let clock = clock.inner.as_ref();
+    let owner = parse_address(owner)?;
+    let contract = make_existing_contract(parse_account_stuff(root_account_stuff_boc)?);
+
+    // NOTE: getter signatures differ between root contract versions
+    let root = nt::core::token_wallet::RootTokenContractState(&contract);
+    let version = root.guess_details(clock).handle_error()?.version;
+    root.get_wallet_address(clock, version, &owner)
+        .map(|address| address.to_string())
+        .handle_error()
+}
+
+pub fn make_root_token_contract_details(
+    data: nt::core::models::RootTokenContractDetails,
+) -> RootTokenContractDetails {
+    ObjectBuilder::new()
+        .set("version", make_token_wallet_version(data.version))
+        .set("name", data.name)
+        .set("symbol", data.symbol)
+        .set("decimals", data.decimals)
+        .set("ownerAddress", data.owner_address.to_string())
+        .set("totalSupply", data.total_supply.to_string())
+        .build()
+        .unchecked_into()
+}
+
+fn make_token_wallet_transaction(
+    data: nt::core::models::TransactionWithData<nt::core::models::TokenWalletTransaction>,
+) -> JsValue {
+    let transaction: JsValue = make_transaction(data.transaction).into();
+    if let Some(info) = data.data {
+        let info = make_token_wallet_transaction_info(info).trust_me();
+        js_sys::Reflect::set(&transaction, &JsValue::from_str("info"), &info).trust_me();
+    }
+    transaction
+}
+
+pub fn make_token_wallet_transaction_info(
+    data: nt::core::models::TokenWalletTransaction,
+) -> Result<JsValue, JsValue> {
+    use nt::core::models::TokenWalletTransaction;
+
+    let (kind, data) = match data {
+        TokenWalletTransaction::IncomingTransfer(data) => (
+            "incoming_transfer",
+            ObjectBuilder::new()
+                .set("tokens", data.tokens.to_string())
+                .set("senderAddress", data.sender_address.to_string())
+                .build(),
+        ),
+        TokenWalletTransaction::OutgoingTransfer(data) => {
+            ("outgoing_transfer", make_token_outgoing_transfer(data))
+        }
+        TokenWalletTransaction::SwapBack(data) => ("swap_back", make_token_swap_back(data)?),
+        TokenWalletTransaction::Accept(tokens) => ("accept", JsValue::from(tokens.to_string())),
+        TokenWalletTransaction::TransferBounced(tokens) => {
+            ("transfer_bounced", JsValue::from(tokens.to_string()))
+        }
+        TokenWalletTransaction::SwapBackBounced(tokens) => {
+            ("swap_back_bounced", JsValue::from(tokens.to_string()))
+        }
+    };
+
+    Ok(ObjectBuilder::new()
+        .set("type", kind)
+        .set("data", data)
+        .build())
+}
+
+pub fn make_token_outgoing_transfer(data: nt::core::models::TokenOutgoingTransfer) -> JsValue {
+    use nt::core::models::TransferRecipient;
+
+    let (kind, address) = match data.to {
+        TransferRecipient::OwnerWallet(address) => ("owner_wallet", address),
+        TransferRecipient::TokenWallet(address) => ("token_wallet", address),
+    };
+
+    ObjectBuilder::new()
+        .set(
+            "to",
+            ObjectBuilder::new()
+                .set("type", kind)
+                .set("address", address.to_string())
+                .build(),
+        )
+        .set("tokens", data.tokens.to_string())
+        .build()
+}
+
+pub fn make_token_swap_back(data: nt::core::models::TokenSwapBack) -> Result<JsValue, JsValue> {
+    Ok(ObjectBuilder::new()
+        .set("tokens", data.tokens.to_string())
+        .set("callbackAddress", data.callback_address.to_string())
+        .set("callbackPayload", serialize_boc(&data.callback_payload)?)
+        .build())
+}
+
+pub fn make_token_wallet_version(
+    version: nt::core::models::TokenWalletVersion,
+) -> TokenWalletVersion {
+    use nt::core::models::TokenWalletVersion;
+
+    JsValue::from(match version {
+        TokenWalletVersion::OldTip3v4 => "OldTip3v4",
+        TokenWalletVersion::Tip3 => "Tip3",
+    })
+    .unchecked_into()
+}
+
+pub fn parse_token_wallet_version(
+    version: TokenWalletVersion,
+) -> Result<nt::core::models::TokenWalletVersion, JsValue> {
+    use nt::core::models::TokenWalletVersion;
+
+    match JsValue::from(version).as_string().as_deref() {
+        Some("OldTip3v4") => Ok(TokenWalletVersion::OldTip3v4),
+        Some("Tip3") => Ok(TokenWalletVersion::Tip3),
+        _ => Err(TokenWalletError::InvalidVersion).handle_error(),
+    }
+}
+
+fn make_token_symbol(data: &nt::core::models::Symbol) -> TokenSymbol {
+    ObjectBuilder::new()
+        .set("name", data.name.clone())
+        .set("fullName", data.full_name.clone())
+        .set("decimals", data.decimals)
+        .set("rootTokenContract", data.root_token_contract.to_string())
+        .build()
+        .unchecked_into()
+}
+
+fn make_internal_message(data: nt::core::InternalMessage) -> Result<InternalMessage, JsValue> {
+    Ok(ObjectBuilder::new()
+        .set("source", data.source.map(|source| source.to_string()))
+        .set("destination", data.destination.to_string())
+        .set("amount", data.amount.to_string())
+        .set("bounce", data.bounce)
+        .set("body", serialize_boc(&data.body.into_cell())?)
+        .build()
+        .unchecked_into())
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const TOKEN_WALLET: &str = r#"
+export type TokenWalletVersion = 'OldTip3v4' | 'Tip3';
+
+export type TokenSymbol = {
+    name: string,
+    fullName: string,
+    decimals: number,
+    rootTokenContract: string,
+};
+
+export type RootTokenContractDetails = {
+    version: TokenWalletVersion,
+    name: string,
+    symbol: string,
+    decimals: number,
+    ownerAddress: string,
+    totalSupply: string,
+};
+
+export type InternalMessage = {
+    source?: string,
+    destination: string,
+    amount: string,
+    bounce: boolean,
+    body: string,
+};
+
+export type TokenWalletTransaction = Transaction & {
+    info?: TokenWalletTransactionInfo,
+};
+
+export type TokenWalletTransactionInfo =
+    | { type: 'incoming_transfer', data: TokenIncomingTransfer }
+    | { type: 'outgoing_transfer', data: TokenOutgoingTransfer }
+    | { type: 'swap_back', data: TokenSwapBack }
+    | { type: 'accept', data: string }
+    | { type: 'transfer_bounced', data: string }
+    | { type: 'swap_back_bounced', data: string };
+
+export type TokenIncomingTransfer = {
+    tokens: string,
+    senderAddress: string,
+};
+
+export type TransferRecipient = {
+    type: 'owner_wallet' | 'token_wallet',
+    address: string,
+};
+
+export type TokenOutgoingTransfer = {
+    to: TransferRecipient,
+    tokens: string,
+};
+
+export type TokenSwapBack = {
+    tokens: string,
+    callbackAddress: string,
+    callbackPayload: string,
+};
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Promise<TokenWallet>")]
+    pub type PromiseTokenWallet;
+
+    #[wasm_bindgen(typescript_type = "Promise<RootTokenContractDetails>")]
+    pub type PromiseRootTokenContractDetails;
+
+    #[wasm_bindgen(typescript_type = "RootTokenContractDetails")]
+    pub type RootTokenContractDetails;
+
+    #[wasm_bindgen(typescript_type = "TokenWalletVersion")]
+    pub type TokenWalletVersion;
+
+    #[wasm_bindgen(typescript_type = "TokenSymbol")]
+    pub type TokenSymbol;
+
+    #[wasm_bindgen(typescript_type = "InternalMessage")]
+    pub type InternalMessage;
+
+    #[wasm_bindgen(typescript_type = "Array<TokenWalletTransaction>")]
+    pub type TokenWalletTransactionsList;
+}
+
+#[derive(thiserror::Error, Debug)]
+enum TokenWalletError {
+    #[error("Invalid token wallet version")]
+    InvalidVersion,
+}
@@ -5,13 +5,14 @@ use nt::utils::*;
 use num_bigint::{BigInt, BigUint};
 use num_traits::Num;
 use ton_block::Serializable;
+use wasm_bindgen::prelude::*;
 use wasm_bindgen::{JsCast, JsValue};
 
 use crate::models::*;
 use crate::utils::*;
 
 pub fn insert_init_data(
-    contract_abi: ton_abi::Contract,
+    contract_abi: &ton_abi::Contract,
     data: ton_types::SliceData,
     public_key: &Option<ed25519_dalek::PublicKey>,
     tokens: TokensObject,
@@ -36,13 +37,13 @@ pub fn insert_init_data(
             return Err(TokensJsonError::ObjectExpected).handle_error();
         }
 
-        for (param_name, param) in contract_abi.data {
+        for (param_name, param) in &contract_abi.data {
             let value = js_sys::Reflect::get(&tokens, &JsValue::from_str(param_name.as_str()))
                 .map_err(|_| TokensJsonError::ParameterNotFound(param_name.clone()))
                 .handle_error()?;
 
             let builder = parse_token_value(&param.value.kind, value)
-                .handle_error()?
+                .map_err(|e| e.with_field(param_name))?
                 .pack_into_chain(&contract_abi.abi_version)
                 .handle_error()?;
 
@@ -120,20 +121,22 @@ pub fn make_token_value(value: ton_abi::TokenValue) -> Result<JsValue, JsValue>
 pub fn parse_tokens_object(
     params: &[ton_abi::Param],
     tokens: TokensObject,
-) -> Result<Vec<ton_abi::Token>, TokensJsonError> {
+) -> Result<Vec<ton_abi::Token>, TokensParseError> {
     if !tokens.is_object() {
-        return Err(TokensJsonError::ObjectExpected);
+        return Err(TokensJsonError::ObjectExpected.into());
     }
     let tokens: js_sys::Object = tokens.unchecked_into();
 
     if js_sys::Object::keys(&tokens).length() != params.len() as u32 {
-        return Err(TokensJsonError::ParameterCountMismatch);
+        return Err(TokensJsonError::ParameterCountMismatch.into());
     }
 
     let mut result = Vec::with_capacity(params.len());
     for param in params {
-        let value = js_sys::Reflect::get(&tokens, &JsValue::from_str(&param.name))
-            .map_err(|_| TokensJsonError::ParameterNotFound(param.name.clone()))?;
+        let value =
+            js_sys::Reflect::get(&tokens, &JsValue::from_str(&param.name)).map_err(|_| {
+                TokensParseError::from(TokensJsonError::ParameterNotFound(param.name.clone()))
+            })?;
         result.push(parse_token(param, value)?);
     }
 
@@ -143,8 +146,8 @@ pub fn parse_tokens_object(
 pub fn parse_token(
     param: &ton_abi::Param,
     token: JsValue,
-) -> Result<ton_abi::Token, TokensJsonError> {
-    let value = parse_token_value(&param.kind, token)?;
+) -> Result<ton_abi::Token, TokensParseError> {
+    let value = parse_token_value(&param.kind, token).map_err(|e| e.with_field(&param.name))?;
     Ok(ton_abi::Token {
         name: param.name.clone(),
         value,
@@ -154,7 +157,14 @@ pub fn parse_token(
 pub fn parse_token_value(
     param: &ton_abi::ParamType,
     value: JsValue,
-) -> Result<ton_abi::TokenValue, TokensJsonError> {
+) -> Result<ton_abi::TokenValue, TokensParseError> {
+    parse_token_value_impl(param, value.clone()).map_err(|e| e.with_context(param, &value))
+}
+
+fn parse_token_value_impl(
+    param: &ton_abi::ParamType,
+    value: JsValue,
+) -> Result<ton_abi::TokenValue, TokensParseError> {
     let value = match param {
         &ton_abi::ParamType::Uint(size) | &ton_abi::ParamType::VarUint(size) => {
             let number = if let Some(value) = value.as_string() {
@@ -169,7 +179,7 @@ pub fn parse_token_value(
                 // Check if there is a conversion error
                 #[allow(clippy::float_cmp)]
                 if value as u64 as f64 != value {
-                    return Err(TokensJsonError::IntegerValueExpected(value));
+                    return Err(TokensJsonError::IntegerValueExpected(value).into());
                 }
 
                 if value >= 0.0 {
@@ -201,7 +211,7 @@ pub fn parse_token_value(
                 // Check if there is a conversion error
                 #[allow(clippy::float_cmp)]
                 if value as i64 as f64 != value {
-                    return Err(TokensJsonError::IntegerValueExpected(value));
+                    return Err(TokensJsonError::IntegerValueExpected(value).into());
                 }
 
                 Ok(BigInt::from(value as i64))
@@ -225,7 +235,7 @@ pub fn parse_token_value(
         }
         ton_abi::ParamType::Array(param) => {
             if !js_sys::Array::is_array(&value) {
-                return Err(TokensJsonError::ArrayExpected);
+                return Err(TokensJsonError::ArrayExpected.into());
             }
             let value: js_sys::Array = value.unchecked_into();
 
@@ -233,25 +243,31 @@ pub fn parse_token_value(
                 *param.clone(),
                 value
                     .iter()
-                    .map(|value| parse_token_value(param.as_ref(), value))
+                    .enumerate()
+                    .map(|(i, value)| {
+                        parse_token_value(param.as_ref(), value).map_err(|e| e.with_index(i))
+                    })
                     .collect::<Result<_, _>>()?,
             )
         }
         ton_abi::ParamType::FixedArray(param, size) => {
             if !js_sys::Array::is_array(&value) {
-                return Err(TokensJsonError::ArrayExpected);
+                return Err(TokensJsonError::ArrayExpected.into());
             }
             let value: js_sys::Array = value.unchecked_into();
 
             if value.length() != *size as u32 {
-                return Err(TokensJsonError::InvalidArrayLength(value.length()));
+                return Err(TokensJsonError::InvalidArrayLength(value.length()).into());
             }
 
             ton_abi::TokenValue::FixedArray(
                 *param.clone(),
                 value
                     .iter()
-                    .map(|value| parse_token_value(param.as_ref(), value))
+                    .enumerate()
+                    .map(|(i, value)| {
+                        parse_token_value(param.as_ref(), value).map_err(|e| e.with_index(i))
+                    })
                     .collect::<Result<_, _>>()?,
             )
         }
@@ -278,25 +294,35 @@ pub fn parse_token_value(
         }
         ton_abi::ParamType::Map(param_key, param_value) => {
             if !js_sys::Array::is_array(&value) {
-                return Err(TokensJsonError::ArrayExpected);
+                return Err(TokensJsonError::ArrayExpected.into());
             }
             let value: js_sys::Array = value.unchecked_into();
 
             let mut result = BTreeMap::new();
 
-            for value in value.iter() {
+            for (i, value) in value.iter().enumerate() {
                 if !js_sys::Array::is_array(&value) {
-                    return Err(TokensJsonError::MapItemExpected);
+                    return Err(
+                        TokensParseError::from(TokensJsonError::MapItemExpected).with_index(i)
+                    );
                 }
                 let value: js_sys::Array = value.unchecked_into();
                 if value.length() != 2 {
-                    return Err(TokensJsonError::MapItemExpected);
+                    return Err(
+                        TokensParseError::from(TokensJsonError::MapItemExpected).with_index(i)
+                    );
                 }
 
-                let key = parse_token_value(param_key.as_ref(), value.get(0))?
+                let key = parse_token_value(param_key.as_ref(), value.get(0))
+                    .map_err(|e| e.with_index(0).with_index(i))?
                     .try_into()
-                    .map_err(|_| TokensJsonError::InvalidMappingKey)?;
-                let value = parse_token_value(param_value.as_ref(), value.get(1))?;
+                    .map_err(|_| {
+                        TokensParseError::from(TokensJsonError::InvalidMappingKey)
+                            .with_index(0)
+                            .with_index(i)
+                    })?;
+                let value = parse_token_value(param_value.as_ref(), value.get(1))
+                    .map_err(|e| e.with_index(1).with_index(i))?;
 
                 result.insert(key, value);
             }
@@ -338,7 +364,7 @@ pub fn parse_token_value(
             }?;
 
             if value.len() != size {
-                return Err(TokensJsonError::InvalidBytesLength(value.len()));
+                return Err(TokensJsonError::InvalidBytesLength(value.len()).into());
             }
 
             ton_abi::TokenValue::FixedBytes(value)
@@ -442,6 +468,144 @@ pub fn parse_token_value(
     Ok(value)
 }
 
+/// Token parsing error with the location of the invalid value
+#[derive(Debug)]
+pub struct TokensParseError {
+    pub error: TokensJsonError,
+    pub path: Vec<TokenPathItem>,
+    pub expected_type: Option<String>,
+    pub value: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum TokenPathItem {
+    Field(String),
+    Index(usize),
+}
+
+impl TokensParseError {
+    pub fn with_field(mut self, name: &str) -> Self {
+        self.path.insert(0, TokenPathItem::Field(name.to_owned()));
+        self
+    }
+
+    pub fn with_index(mut self, index: usize) -> Self {
+        self.path.insert(0, TokenPathItem::Index(index));
+        self
+    }
+
+    fn with_context(mut self, param: &ton_abi::ParamType, value: &JsValue) -> Self {
+        // Only the innermost value is interesting
+        if self.expected_type.is_none() {
+            self.expected_type = Some(make_param_type_name(param));
+            self.value = js_sys::JSON::stringify(value)
+                .ok()
+                .and_then(|value| value.as_string());
+        }
+        self
+    }
+
+    pub fn path(&self) -> String {
+        let mut result = String::new();
+        for item in &self.path {
+            match item {
+                TokenPathItem::Field(name) if result.is_empty() => result.push_str(name),
+                TokenPathItem::Field(name) => {
+                    result.push('.');
+                    result.push_str(name);
+                }
+                TokenPathItem::Index(index) => {
+                    result.push('[');
+                    result.push_str(&index.to_string());
+                    result.push(']');
+                }
+            }
+        }
+        result
+    }
+}
+
+impl From<TokensJsonError> for TokensParseError {
+    fn from(error: TokensJsonError) -> Self {
+        Self {
+            error,
+            path: Vec::new(),
+            expected_type: None,
+            value: None,
+        }
+    }
+}
+
+impl std::fmt::Display for TokensParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            self.error.fmt(f)
+        } else {
+            write!(f, "{} (at {})", self.error, self.path())
+        }
+    }
+}
+
+impl std::error::Error for TokensParseError {}
+
+impl From<TokensParseError> for JsValue {
+    fn from(e: TokensParseError) -> Self {
+        let error = js_sys::Error::new(&e.to_string());
+        error.set_name("TokensParseError");
+
+        let error = JsValue::from(error);
+        for (key, value) in [
+            ("code", JsValue::from(e.error.code())),
+            ("path", JsValue::from(e.path())),
+            ("expectedType", JsValue::from(e.expected_type)),
+            ("value", JsValue::from(e.value)),
+        ] {
+            js_sys::Reflect::set(&error, &JsValue::from_str(key), &value).trust_me();
+        }
+        error
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const TOKENS_PARSE_ERROR: &str = r#"
+export type TokensParseErrorCode =
+    | 'ParameterCountMismatch'
+    | 'ObjectExpected'
+    | 'MessageExpected'
+    | 'MessageBodyExpected'
+    | 'ArrayExpected'
+    | 'ParameterNotFound'
+    | 'InvalidNumber'
+    | 'IntegerValueExpected'
+    | 'UnsignedValueExpected'
+    | 'NumberExpected'
+    | 'BoolExpected'
+    | 'InvalidArrayLength'
+    | 'InvalidCell'
+    | 'StringExpected'
+    | 'BocExpected'
+    | 'MapItemExpected'
+    | 'InvalidMappingKey'
+    | 'InvalidAddress'
+    | 'InvalidBytes'
+    | 'InvalidBytesLength'
+    | 'InvalidPublicKey'
+    | 'ParamTypeExpected'
+    | 'InvalidComponents';
+
+/**
+ * A plain `Error` thrown on invalid tokens, which can be
+ * distinguished by its `name` (there is no such class at runtime)
+ */
+export type TokensParseError = Error & {
+    name: 'TokensParseError',
+    code: TokensParseErrorCode,
+    path: string,
+    expectedType?: string,
+    value?: string,
+};
+"#;
+
 pub fn parse_params_list(params: ParamsList) -> Result<Vec<ton_abi::Param>, TokensJsonError> {
     if !js_sys::Array::is_array(&params) {
         return Err(TokensJsonError::ObjectExpected);
@@ -585,3 +749,100 @@ pub fn parse_param_type(kind: &str) -> Result<ton_abi::ParamType, TokensJsonErro
 
     Ok(result)
 }
+
+pub fn make_param_type_name(kind: &ton_abi::ParamType) -> String {
+    match kind {
+        ton_abi::ParamType::Uint(size) => format!("uint{size}"),
+        ton_abi::ParamType::Int(size) => format!("int{size}"),
+        ton_abi::ParamType::VarUint(size) => format!("varuint{size}"),
+        ton_abi::ParamType::VarInt(size) => format!("varint{size}"),
+        ton_abi::ParamType::Bool => "bool".to_owned(),
+        ton_abi::ParamType::Tuple(_) => "tuple".to_owned(),
+        ton_abi::ParamType::Array(kind) => format!("{}[]", make_param_type_name(kind)),
+        ton_abi::ParamType::FixedArray(kind, size) => {
+            format!("{}[{size}]", make_param_type_name(kind))
+        }
+        ton_abi::ParamType::Cell => "cell".to_owned(),
+        ton_abi::ParamType::Map(key, value) => format!(
+            "map({},{})",
+            make_param_type_name(key),
+            make_param_type_name(value)
+        ),
+        ton_abi::ParamType::Address => "address".to_owned(),
+        ton_abi::ParamType::Bytes => "bytes".to_owned(),
+        ton_abi::ParamType::FixedBytes(size) => format!("fixedbytes{size}"),
+        ton_abi::ParamType::String => "string".to_owned(),
+        ton_abi::ParamType::Token => "gram".to_owned(),
+        ton_abi::ParamType::Time => "time".to_owned(),
+        ton_abi::ParamType::Expire => "expire".to_owned(),
+        ton_abi::ParamType::PublicKey => "pubkey".to_owned(),
+        ton_abi::ParamType::Optional(kind) => format!("optional({})", make_param_type_name(kind)),
+        ton_abi::ParamType::Ref(kind) => format!("ref({})", make_param_type_name(kind)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_is_built_from_outermost_item() {
+        let error = TokensParseError::from(TokensJsonError::BoolExpected)
+            .with_field("flag")
+            .with_index(1)
+            .with_field("items")
+            .with_field("params");
+        assert_eq!(error.path(), "params.items[1].flag");
+        assert_eq!(
+            error.to_string(),
+            "Expected boolean (at params.items[1].flag)"
+        );
+    }
+
+    #[test]
+    fn path_is_omitted_for_top_level_errors() {
+        let error = TokensParseError::from(TokensJsonError::ParameterCountMismatch);
+        assert_eq!(error.path(), "");
+        assert_eq!(error.to_string(), "Parameter count mismatch");
+    }
+
+    #[test]
+    fn missing_parameter_is_reported_at_parent_path() {
+        let error = TokensParseError::from(TokensJsonError::ParameterNotFound("value".to_owned()))
+            .with_field("inner");
+        assert_eq!(error.to_string(), "Parameter not found: value (at inner)");
+    }
+
+    #[test]
+    fn all_error_codes_are_typed() {
+        let errors = [
+            TokensJsonError::ParameterCountMismatch,
+            TokensJsonError::ObjectExpected,
+            TokensJsonError::MessageExpected,
+            TokensJsonError::MessageBodyExpected,
+            TokensJsonError::ArrayExpected,
+            TokensJsonError::ParameterNotFound(String::new()),
+            TokensJsonError::InvalidNumber(String::new()),
+            TokensJsonError::IntegerValueExpected(0.0),
+            TokensJsonError::UnsignedValueExpected(0.0),
+            TokensJsonError::NumberExpected,
+            TokensJsonError::BoolExpected,
+            TokensJsonError::InvalidArrayLength(0),
+            TokensJsonError::InvalidCell,
+            TokensJsonError::StringExpected,
+            TokensJsonError::BocExpected,
+            TokensJsonError::MapItemExpected,
+            TokensJsonError::InvalidMappingKey,
+            TokensJsonError::InvalidAddress,
+            TokensJsonError::InvalidBytes,
+            TokensJsonError::InvalidBytesLength(0),
+            TokensJsonError::InvalidPublicKey,
+            TokensJsonError::ParamTypeExpected,
+            TokensJsonError::InvalidComponents,
+        ];
+        for error in errors {
+            let code = format!("'{}'", error.code());
+            assert!(TOKENS_PARSE_ERROR.contains(&code), "{code} is not typed");
+        }
+    }
+}
@@ -0,0 +1,1362 @@
+use std::borrow::Cow;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use nt::core::ton_wallet::{
+    ever_wallet, highload_wallet_v2, multisig, wallet_v3, Gift, MultisigType, TransferAction,
+};
+use nt::utils::TrustMe;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::*;
+
+use crate::models::*;
+use crate::transport::TransportHandle;
+use crate::utils::*;
+
+#[wasm_bindgen(js_name = "walletPrepareTransferHighloadV2")]
+pub fn wallet_prepare_transfer_highload_v2(
+    clock: &ClockWithOffset,
+    account_stuff_boc: &Boc,
+    public_key: &str,
+    gifts: GiftList,
+    timeout: u32,
+) -> Result<UnsignedMessage, JsValue> {
+    let account_stuff = parse_account_stuff(account_stuff_boc)?;
+    let public_key = parse_public_key(public_key)?;
+    let gifts = parse_gifts(gifts)?;
+    if gifts.len() > HIGHLOAD_WALLET_V2_MAX_GIFTS {
+        return Err(TonWalletError::TooManyGifts).handle_error();
+    }
+    let expiration = nt::core::models::Expiration::Timeout(timeout);
+
+    // NOTE: query id is derived from the expiration timestamp,
+    // and `last_cleaned` is taken from the current account data
+    let action = highload_wallet_v2::prepare_transfer(
+        clock.inner.as_ref(),
+        &public_key,
+        &account_stuff,
+        gifts,
+        expiration,
+    )
+    .handle_error()?;
+
+    make_transfer_message(action)
+}
+
+#[wasm_bindgen(js_name = "walletV3PrepareTransfer")]
+pub fn wallet_v3_prepare_transfer(
+    clock: &ClockWithOffset,
+    account_stuff_boc: &Boc,
+    public_key: &str,
+    gifts: GiftList,
+    timeout: u32,
+) -> Result<UnsignedMessage, JsValue> {
+    let account_stuff = parse_account_stuff(account_stuff_boc)?;
+    let public_key = parse_public_key(public_key)?;
+    let gifts = parse_gifts(gifts)?;
+    let expiration = nt::core::models::Expiration::Timeout(timeout);
+
+    let action = wallet_v3::prepare_transfer(
+        clock.inner.as_ref(),
+        &public_key,
+        &account_stuff,
+        0,
+        gifts,
+        expiration,
+    )
+    .handle_error()?;
+
+    make_transfer_message(action)
+}
+
+#[wasm_bindgen(js_name = "walletV3ComputeAddress")]
+pub fn wallet_v3_compute_address(public_key: &str, workchain: i8) -> Result<String, JsValue> {
+    let public_key = parse_public_key(public_key)?;
+    Ok(wallet_v3::compute_contract_address(&public_key, workchain).to_string())
+}
+
+#[wasm_bindgen(js_name = "walletV3ParseState")]
+pub fn wallet_v3_parse_state(account_stuff_boc: &Boc) -> Result<WalletV3State, JsValue> {
+    let account_stuff = parse_account_stuff(account_stuff_boc)?;
+
+    let data = match &account_stuff.storage.state {
+        ton_block::AccountState::AccountActive { state_init } => match &state_init.data {
+            Some(data) => data,
+            None => return Err(nt::abi::ExtractionError::AccountDataNotFound).handle_error(),
+        },
+        _ => return Err(nt::abi::ExtractionError::AccountIsNotActive).handle_error(),
+    };
+
+    let init_data = wallet_v3::InitData::try_from(data).handle_error()?;
+    Ok(ObjectBuilder::new()
+        .set("seqno", init_data.seqno)
+        .set("walletId", init_data.wallet_id)
+        .set("publicKey", init_data.public_key.to_hex_string())
+        .build()
+        .unchecked_into())
+}
+
+#[wasm_bindgen(js_name = "computeWalletAddress")]
+pub fn compute_wallet_address(
+    wallet_type: WalletType,
+    public_key: &str,
+    workchain: i8,
+) -> Result<String, JsValue> {
+    let wallet_type = parse_wallet_type(wallet_type)?;
+    let public_key = parse_public_key(public_key)?;
+    Ok(nt::core::ton_wallet::compute_address(&public_key, wallet_type, workchain).to_string())
+}
+
+#[wasm_bindgen(js_name = "walletPrepareDeploy")]
+pub fn wallet_prepare_deploy(
+    clock: &ClockWithOffset,
+    wallet_type: WalletType,
+    public_key: &str,
+    workchain: i8,
+    timeout: u32,
+    params: Option<MultisigDeployParams>,
+) -> Result<UnsignedMessage, JsValue> {
+    use nt::core::ton_wallet::WalletType;
+
+    let clock = clock.inner.as_ref();
+    let wallet_type = parse_wallet_type(wallet_type)?;
+    let public_key = parse_public_key(public_key)?;
+    let expiration = nt::core::models::Expiration::Timeout(timeout);
+
+    let inner = match wallet_type {
+        WalletType::WalletV3 => {
+            wallet_v3::prepare_deploy(clock, &public_key, workchain, expiration)
+        }
+        WalletType::HighloadWalletV2 => {
+            highload_wallet_v2::prepare_deploy(clock, &public_key, workchain, expiration)
+        }
+        WalletType::EverWallet => {
+            ever_wallet::prepare_deploy(clock, &public_key, workchain, expiration)
+        }
+        WalletType::Multisig(multisig_type) => {
+            let params = parse_multisig_deploy_params(params, &public_key)?;
+            multisig::prepare_deploy(
+                clock,
+                &public_key,
+                multisig_type,
+                workchain,
+                expiration,
+                multisig::DeployParams {
+                    owners: &params.owners,
+                    req_confirms: params.req_confirms,
+                    expiration_time: params.expiration_time,
+                },
+            )
+        }
+    }
+    .handle_error()?;
+
+    Ok(UnsignedMessage { inner })
+}
+
+#[wasm_bindgen(js_name = "walletPrepareTransfer")]
+pub fn wallet_prepare_transfer(
+    clock: &ClockWithOffset,
+    wallet_type: WalletType,
+    account_stuff_boc: &Boc,
+    public_key: &str,
+    gifts: GiftList,
+    timeout: u32,
+) -> Result<UnsignedMessage, JsValue> {
+    let wallet_type = parse_wallet_type(wallet_type)?;
+    let account_stuff = parse_account_stuff(account_stuff_boc)?;
+    let public_key = parse_public_key(public_key)?;
+    let gifts = parse_gifts(gifts)?;
+    let expiration = nt::core::models::Expiration::Timeout(timeout);
+
+    prepare_transfer_action(
+        clock.inner.as_ref(),
+        wallet_type,
+        &account_stuff,
+        &public_key,
+        gifts,
+        expiration,
+    )
+    .and_then(make_transfer_message)
+}
+
+#[wasm_bindgen(js_name = "prepareConfirmTransaction")]
+pub fn prepare_confirm_transaction(
+    clock: &ClockWithOffset,
+    wallet_type: WalletType,
+    public_key: &str,
+    address: &str,
+    transaction_id: &str,
+    timeout: u32,
+) -> Result<UnsignedMessage, JsValue> {
+    let multisig_type = parse_multisig_type(wallet_type)?;
+    let public_key = parse_public_key(public_key)?;
+    let address = parse_address(address)?;
+    let transaction_id = u64::from_str(transaction_id.trim())
+        .map_err(|_| "Invalid transaction id")
+        .handle_error()?;
+    let expiration = nt::core::models::Expiration::Timeout(timeout);
+
+    let inner = multisig::prepare_confirm_transaction(
+        clock.inner.as_ref(),
+        multisig_type,
+        &public_key,
+        address,
+        transaction_id,
+        expiration,
+    )
+    .handle_error()?;
+
+    Ok(UnsignedMessage { inner })
+}
+
+#[wasm_bindgen(js_name = "getCustodians")]
+pub fn get_custodians(
+    clock: &ClockWithOffset,
+    wallet_type: WalletType,
+    account_stuff_boc: &Boc,
+) -> Result<StringArray, JsValue> {
+    let multisig_type = parse_multisig_type(wallet_type)?;
+    let account_stuff = parse_account_stuff(account_stuff_boc)?;
+
+    let custodians = multisig::get_custodians(
+        clock.inner.as_ref(),
+        multisig_type,
+        Cow::Owned(account_stuff),
+    )
+    .handle_error()?;
+
+    Ok(make_custodians(custodians.iter()))
+}
+
+#[wasm_bindgen(js_name = "getUnconfirmedTransactions")]
+pub fn get_unconfirmed_transactions(
+    clock: &ClockWithOffset,
+    wallet_type: WalletType,
+    account_stuff_boc: &Boc,
+) -> Result<MultisigPendingTransactionList, JsValue> {
+    let clock = clock.inner.as_ref();
+    let multisig_type = parse_multisig_type(wallet_type)?;
+    let account_stuff = parse_account_stuff(account_stuff_boc)?;
+
+    let custodians = multisig::get_custodians(clock, multisig_type, Cow::Borrowed(&account_stuff))
+        .handle_error()?;
+    let transactions = multisig::get_pending_transactions(
+        clock,
+        multisig_type,
+        Cow::Owned(account_stuff),
+        &custodians,
+    )
+    .handle_error()?;
+
+    transactions
+        .into_iter()
+        .map(make_multisig_pending_transaction)
+        .collect::<Result<js_sys::Array, JsValue>>()
+        .map(JsCast::unchecked_into)
+}
+
+fn make_multisig_pending_transaction(
+    data: nt::core::models::MultisigPendingTransaction,
+) -> Result<JsValue, JsValue> {
+    Ok(ObjectBuilder::new()
+        .set("id", data.id.to_string())
+        .set(
+            "confirmations",
+            data.confirmations
+                .iter()
+                .map(|item| JsValue::from(item.to_hex_string()))
+                .collect::<js_sys::Array>(),
+        )
+        .set("signsRequired", data.signs_required)
+        .set("signsReceived", data.signs_received)
+        .set("creator", data.creator.to_hex_string())
+        .set("index", data.index)
+        .set("dest", data.dest.to_string())
+        .set("value", data.value.to_string())
+        .set("sendFlags", data.send_flags)
+        .set("payload", serialize_boc(&data.payload)?)
+        .set("bounce", data.bounce)
+        .build())
+}
+
+/// Detects one of the wallet types supported by `WalletType` or wallet v4r2.
+///
+/// Returns `undefined` for accounts with unknown code
+#[wasm_bindgen(js_name = "detectWalletType")]
+pub fn detect_wallet_type(
+    clock: &ClockWithOffset,
+    account_stuff_boc: &Boc,
+) -> Result<Option<DetectedWallet>, JsValue> {
+    let account_stuff = parse_account_stuff(account_stuff_boc)?;
+    if let Some(public_key) = extract_wallet_v4_public_key(&account_stuff)? {
+        // NOTE: wallet v4 accepts the same transfers as wallet v3
+        let details = nt::core::ton_wallet::WalletType::WalletV3.details();
+        return Ok(Some(
+            ObjectBuilder::new()
+                .set("walletType", "WalletV4R2")
+                .set("publicKey", hex::encode(public_key.as_bytes()))
+                .set("details", make_wallet_details(details))
+                .build()
+                .unchecked_into(),
+        ));
+    }
+
+    if guess_account_wallet_type(&account_stuff).is_none() {
+        return Ok(None);
+    }
+
+    let contract = make_existing_contract(account_stuff);
+    let (public_key, wallet_type) =
+        nt::core::ton_wallet::extract_wallet_init_data(&contract).handle_error()?;
+
+    let result = ObjectBuilder::new()
+        .set("walletType", make_wallet_type(wallet_type))
+        .set("publicKey", hex::encode(public_key.as_bytes()))
+        .set("details", make_wallet_details(wallet_type.details()));
+
+    let result = match wallet_type {
+        nt::core::ton_wallet::WalletType::Multisig(multisig_type) => {
+            let custodians = multisig::get_custodians(
+                clock.inner.as_ref(),
+                multisig_type,
+                Cow::Owned(contract.account),
+            )
+            .handle_error()?;
+
+            result.set("custodians", make_custodians(custodians.iter()))
+        }
+        _ => result,
+    };
+
+    Ok(Some(result.build().unchecked_into()))
+}
+
+/// Guesses the wallet type by the code hash of the active account
+pub fn guess_account_wallet_type(
+    account_stuff: &ton_block::AccountStuff,
+) -> Option<nt::core::ton_wallet::WalletType> {
+    match &account_stuff.storage.state {
+        ton_block::AccountState::AccountActive { state_init, .. } => state_init
+            .code
+            .as_ref()
+            .and_then(|code| guess_wallet_type(&code.repr_hash())),
+        _ => None,
+    }
+}
+
+pub fn guess_wallet_type(
+    code_hash: &ton_types::UInt256,
+) -> Option<nt::core::ton_wallet::WalletType> {
+    if let Some(multisig_type) = multisig::guess_multisig_type(code_hash) {
+        Some(nt::core::ton_wallet::WalletType::Multisig(multisig_type))
+    } else if wallet_v3::is_wallet_v3(code_hash) {
+        Some(nt::core::ton_wallet::WalletType::WalletV3)
+    } else if highload_wallet_v2::is_highload_wallet_v2(code_hash) {
+        Some(nt::core::ton_wallet::WalletType::HighloadWalletV2)
+    } else if ever_wallet::is_ever_wallet(code_hash) {
+        Some(nt::core::ton_wallet::WalletType::EverWallet)
+    } else {
+        None
+    }
+}
+
+/// Extracts the public key of the wallet v4r2 account.
+///
+/// Wallet v4 is not a `WalletType` in nekoton yet, so it is detected here by
+/// the code hash. Its data starts with `seqno:uint32 subwallet_id:uint32 public_key:bits256`
+fn extract_wallet_v4_public_key(
+    account_stuff: &ton_block::AccountStuff,
+) -> Result<Option<ed25519_dalek::PublicKey>, JsValue> {
+    let state_init = match &account_stuff.storage.state {
+        ton_block::AccountState::AccountActive { state_init, .. } => state_init,
+        _ => return Ok(None),
+    };
+    match &state_init.code {
+        Some(code) if code.repr_hash().as_slice() == &WALLET_V4R2_CODE_HASH => {}
+        _ => return Ok(None),
+    }
+
+    let mut data = ton_types::SliceData::from(state_init.data.clone().unwrap_or_default());
+    data.move_by(64).handle_error()?;
+    let public_key = data.get_next_bytes(32).handle_error()?;
+    ed25519_dalek::PublicKey::from_bytes(&public_key)
+        .map(Some)
+        .handle_error()
+}
+
+const WALLET_V4R2_CODE_HASH: [u8; 32] = [
+    0xfe, 0xb5, 0xff, 0x68, 0x20, 0xe2, 0xff, 0x0d, 0x94, 0x83, 0xe7, 0xe0, 0xd6, 0x2c, 0x81, 0x7d,
+    0x84, 0x67, 0x89, 0xfb, 0x4a, 0xe5, 0x80, 0xc8, 0x78, 0x86, 0x6d, 0x95, 0x9d, 0xab, 0xd5, 0xc0,
+];
+
+fn make_wallet_details(data: nt::core::ton_wallet::TonWalletDetails) -> JsValue {
+    ObjectBuilder::new()
+        .set("requiresSeparateDeploy", data.requires_separate_deploy)
+        .set("minAmount", data.min_amount.to_string())
+        .set("maxMessages", data.max_messages as u32)
+        .set("supportsPayload", data.supports_payload)
+        .set("supportsStateInit", data.supports_state_init)
+        .set("supportsMultipleOwners", data.supports_multiple_owners)
+        .set("supportsCodeUpdate", data.supports_code_update)
+        .set("expirationTime", data.expiration_time)
+        .set(
+            "requiredConfirmations",
+            data.required_confirmations.map(|value| value.get()),
+        )
+        .build()
+}
+
+#[wasm_bindgen]
+pub struct TonWallet {
+    #[wasm_bindgen(skip)]
+    pub address: String,
+    #[wasm_bindgen(skip)]
+    pub public_key: ed25519_dalek::PublicKey,
+    #[wasm_bindgen(skip)]
+    pub wallet_type: nt::core::ton_wallet::WalletType,
+    #[wasm_bindgen(skip)]
+    pub inner: Arc<TonWalletState>,
+}
+
+impl TonWallet {
+    pub fn new(
+        clock: Arc<nt::utils::ClockWithOffset>,
+        transport: TransportHandle,
+        wallet: nt::core::ton_wallet::TonWallet,
+    ) -> Self {
+        Self {
+            address: wallet.address().to_string(),
+            public_key: *wallet.public_key(),
+            wallet_type: wallet.wallet_type(),
+            inner: Arc::new(TonWalletState {
+                clock,
+                transport,
+                wallet: Mutex::new(wallet),
+            }),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl TonWallet {
+    #[wasm_bindgen(getter, js_name = "address")]
+    pub fn address(&self) -> String {
+        self.address.clone()
+    }
+
+    #[wasm_bindgen(getter, js_name = "publicKey")]
+    pub fn public_key(&self) -> String {
+        hex::encode(self.public_key.as_bytes())
+    }
+
+    #[wasm_bindgen(getter, js_name = "walletType")]
+    pub fn wallet_type(&self) -> WalletType {
+        make_wallet_type(self.wallet_type)
+    }
+
+    #[wasm_bindgen(js_name = "contractState")]
+    pub fn contract_state(&self) -> ContractState {
+        make_contract_state(*self.inner.wallet.lock().trust_me().contract_state())
+    }
+
+    #[wasm_bindgen(js_name = "details")]
+    pub fn details(&self) -> TonWalletDetails {
+        make_wallet_details(self.inner.wallet.lock().trust_me().details()).unchecked_into()
+    }
+
+    #[wasm_bindgen(js_name = "custodians")]
+    pub fn custodians(&self) -> Option<StringArray> {
+        let wallet = self.inner.wallet.lock().trust_me();
+        wallet
+            .get_custodians()
+            .as_ref()
+            .map(|custodians| make_custodians(custodians.iter()))
+    }
+
+    #[wasm_bindgen(js_name = "unconfirmedTransactions")]
+    pub fn unconfirmed_transactions(&self) -> Result<MultisigPendingTransactionList, JsValue> {
+        let wallet = self.inner.wallet.lock().trust_me();
+        wallet
+            .get_unconfirmed_transactions()
+            .iter()
+            .cloned()
+            .map(make_multisig_pending_transaction)
+            .collect::<Result<js_sys::Array, JsValue>>()
+            .map(JsCast::unchecked_into)
+    }
+
+    #[wasm_bindgen(js_name = "prepareTransfer")]
+    pub fn prepare_transfer(
+        &self,
+        gifts: GiftList,
+        timeout: u32,
+    ) -> Result<PromiseUnsignedMessage, JsValue> {
+        let inner = self.inner.clone();
+        let address = parse_address(&self.address)?;
+        let public_key = self.public_key;
+        let wallet_type = self.wallet_type;
+        let gifts = parse_gifts(gifts)?;
+        let expiration = nt::core::models::Expiration::Timeout(timeout);
+
+        Ok(JsCast::unchecked_into(future_to_promise(async move {
+            let account_stuff = match inner
+                .transport
+                .as_ref()
+                .get_contract_state(&address)
+                .await
+                .handle_error()?
+            {
+                nt::transport::models::RawContractState::Exists(contract) => contract.account,
+                nt::transport::models::RawContractState::NotExists => {
+                    return Err(TonWalletError::DeployFirst).handle_error()
+                }
+            };
+
+            prepare_transfer_action(
+                inner.clock.as_ref(),
+                wallet_type,
+                &account_stuff,
+                &public_key,
+                gifts,
+                expiration,
+            )
+            .and_then(make_transfer_message)
+            .map(JsValue::from)
+        })))
+    }
+
+    #[wasm_bindgen(js_name = "estimateFees")]
+    pub fn estimate_fees(&self, message: SignedMessage) -> Result<PromiseString, JsValue> {
+        let inner = self.inner.clone();
+        let message = parse_signed_message(message)?;
+
+        // NOTE: method must be called through the external mutex
+        #[allow(clippy::await_holding_lock)]
+        Ok(JsCast::unchecked_into(future_to_promise(async move {
+            let mut wallet = inner.wallet.lock().trust_me();
+
+            let fees = wallet
+                .estimate_fees(&message.message)
+                .await
+                .handle_error()?;
+            Ok(JsValue::from(fees.to_string()))
+        })))
+    }
+
+    #[wasm_bindgen(js_name = "sendMessage")]
+    pub fn send_message(
+        &self,
+        message: SignedMessage,
+    ) -> Result<PromisePendingTransaction, JsValue> {
+        let inner = self.inner.clone();
+        let message = parse_signed_message(message)?;
+
+        // NOTE: method must be called through the external mutex
+        #[allow(clippy::await_holding_lock)]
+        Ok(JsCast::unchecked_into(future_to_promise(async move {
+            let mut wallet = inner.wallet.lock().trust_me();
+
+            let pending_transaction = wallet
+                .send(&message.message, message.expire_at)
+                .await
+                .handle_error()?;
+            Ok(make_pending_transaction(pending_transaction).unchecked_into())
+        })))
+    }
+
+    #[wasm_bindgen(js_name = "refresh")]
+    pub fn refresh(&mut self) -> PromiseVoid {
+        let inner = self.inner.clone();
+
+        // NOTE: method must be called through the external mutex
+        #[allow(clippy::await_holding_lock)]
+        JsCast::unchecked_into(future_to_promise(async move {
+            let mut wallet = inner.wallet.lock().trust_me();
+
+            wallet.refresh().await.handle_error()?;
+            Ok(JsValue::undefined())
+        }))
+    }
+
+    #[wasm_bindgen(js_name = "handleBlock")]
+    pub fn handle_block(&mut self, block_id: String) -> PromiseVoid {
+        let inner = self.inner.clone();
+
+        // NOTE: method must be called through the external mutex
+        #[allow(clippy::await_holding_lock)]
+        JsCast::unchecked_into(future_to_promise(async move {
+            let block = inner.transport.get_block(&block_id).await?;
+
+            let mut wallet = inner.wallet.lock().trust_me();
+            match block {
+                Some(block) => wallet.handle_block(&block).await.handle_error()?,
+                None => wallet.refresh().await.handle_error()?,
+            }
+
+            Ok(JsValue::undefined())
+        }))
+    }
+
+    #[wasm_bindgen(js_name = "preloadTransactions")]
+    pub fn preload_transactions(&mut self, lt: &str) -> Result<PromiseVoid, JsValue> {
+        let from_lt = u64::from_str(lt).handle_error()?;
+
+        let inner = self.inner.clone();
+
+        // NOTE: method must be called through the external mutex
+        #[allow(clippy::await_holding_lock)]
+        Ok(JsCast::unchecked_into(future_to_promise(async move {
+            let mut wallet = inner.wallet.lock().trust_me();
+
+            wallet.preload_transactions(from_lt).await.handle_error()?;
+            Ok(JsValue::undefined())
+        })))
+    }
+
+    #[wasm_bindgen(getter, js_name = "pollingMethod")]
+    pub fn polling_method(&self) -> PollingMethod {
+        make_polling_method(self.inner.wallet.lock().trust_me().polling_method())
+    }
+}
+
+pub struct TonWalletState {
+    clock: Arc<nt::utils::ClockWithOffset>,
+    transport: TransportHandle,
+    wallet: Mutex<nt::core::ton_wallet::TonWallet>,
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = "TonWalletSubscriptionHandler")]
+    pub type TonWalletSubscriptionHandlerImpl;
+
+    #[wasm_bindgen(method, js_name = "onMessageSent")]
+    pub fn on_message_sent(
+        this: &TonWalletSubscriptionHandlerImpl,
+        pending_transaction: PendingTransaction,
+        transaction: Option<Transaction>,
+    );
+
+    #[wasm_bindgen(method, js_name = "onMessageExpired")]
+    pub fn on_message_expired(
+        this: &TonWalletSubscriptionHandlerImpl,
+        pending_transaction: PendingTransaction,
+    );
+
+    #[wasm_bindgen(method, js_name = "onStateChanged")]
+    pub fn on_state_changed(this: &TonWalletSubscriptionHandlerImpl, new_state: ContractState);
+
+    #[wasm_bindgen(method, js_name = "onTransactionsFound")]
+    pub fn on_transactions_found(
+        this: &TonWalletSubscriptionHandlerImpl,
+        transactions: TonWalletTransactionsList,
+        batch_info: TransactionsBatchInfo,
+    );
+
+    #[wasm_bindgen(method, js_name = "onUnconfirmedTransactionsChanged")]
+    pub fn on_unconfirmed_transactions_changed(
+        this: &TonWalletSubscriptionHandlerImpl,
+        unconfirmed_transactions: MultisigPendingTransactionList,
+    );
+
+    #[wasm_bindgen(method, js_name = "onCustodiansChanged")]
+    pub fn on_custodians_changed(this: &TonWalletSubscriptionHandlerImpl, custodians: StringArray);
+
+    #[wasm_bindgen(method, js_name = "onDetailsChanged")]
+    pub fn on_details_changed(this: &TonWalletSubscriptionHandlerImpl, details: TonWalletDetails);
+}
+
+unsafe impl Send for TonWalletSubscriptionHandlerImpl {}
+unsafe impl Sync for TonWalletSubscriptionHandlerImpl {}
+
+pub struct TonWalletSubscriptionHandler {
+    inner: TonWalletSubscriptionHandlerImpl,
+}
+
+impl From<TonWalletSubscriptionHandlerImpl> for TonWalletSubscriptionHandler {
+    fn from(inner: TonWalletSubscriptionHandlerImpl) -> Self {
+        Self { inner }
+    }
+}
+
+impl nt::core::ton_wallet::TonWalletSubscriptionHandler for TonWalletSubscriptionHandler {
+    fn on_message_sent(
+        &self,
+        pending_transaction: nt::core::models::PendingTransaction,
+        transaction: Option<nt::core::models::Transaction>,
+    ) {
+        self.inner.on_message_sent(
+            make_pending_transaction(pending_transaction),
+            transaction.map(make_transaction),
+        );
+    }
+
+    fn on_message_expired(&self, pending_transaction: nt::core::models::PendingTransaction) {
+        self.inner
+            .on_message_expired(make_pending_transaction(pending_transaction));
+    }
+
+    fn on_state_changed(&self, new_state: nt::core::models::ContractState) {
+        self.inner.on_state_changed(make_contract_state(new_state));
+    }
+
+    fn on_transactions_found(
+        &self,
+        transactions: Vec<
+            nt::core::models::TransactionWithData<nt::core::models::TransactionAdditionalInfo>,
+        >,
+        batch_info: nt::core::models::TransactionsBatchInfo,
+    ) {
+        self.inner.on_transactions_found(
+            transactions
+                .into_iter()
+                .map(make_ton_wallet_transaction)
+                .collect::<js_sys::Array>()
+                .unchecked_into(),
+            make_transactions_batch_info(batch_info),
+        );
+    }
+
+    fn on_unconfirmed_transactions_changed(
+        &self,
+        unconfirmed_transactions: Vec<nt::core::models::MultisigPendingTransaction>,
+    ) {
+        self.inner.on_unconfirmed_transactions_changed(
+            unconfirmed_transactions
+                .into_iter()
+                .map(|item| make_multisig_pending_transaction(item).trust_me())
+                .collect::<js_sys::Array>()
+                .unchecked_into(),
+        );
+    }
+
+    fn on_custodians_changed(&self, custodians: Vec<ton_types::UInt256>) {
+        self.inner
+            .on_custodians_changed(make_custodians(custodians.iter()));
+    }
+
+    fn on_details_changed(&self, details: nt::core::ton_wallet::TonWalletDetails) {
+        self.inner
+            .on_details_changed(make_wallet_details(details).unchecked_into());
+    }
+}
+
+fn make_ton_wallet_transaction(
+    data: nt::core::models::TransactionWithData<nt::core::models::TransactionAdditionalInfo>,
+) -> JsValue {
+    let transaction: JsValue = make_transaction(data.transaction).into();
+    if let Some(info) = data.data {
+        let info = make_transaction_additional_info(info).trust_me();
+        js_sys::Reflect::set(&transaction, &JsValue::from_str("info"), &info).trust_me();
+    }
+    transaction
+}
+
+pub fn make_transaction_additional_info(
+    data: nt::core::models::TransactionAdditionalInfo,
+) -> Result<JsValue, JsValue> {
+    use nt::core::models::TransactionAdditionalInfo;
+
+    let (kind, data) = match data {
+        TransactionAdditionalInfo::Comment(comment) => ("comment", JsValue::from(comment)),
+        TransactionAdditionalInfo::DePoolOnRoundComplete(data) => (
+            "de_pool_on_round_complete",
+            ObjectBuilder::new()
+                .set("roundId", data.round_id.to_string())
+                .set("reward", data.reward.to_string())
+                .set("ordinaryStake", data.ordinary_stake.to_string())
+                .set("vestingStake", data.vesting_stake.to_string())
+                .set("lockStake", data.lock_stake.to_string())
+                .set("reinvest", data.reinvest)
+                .set("reason", data.reason)
+                .build(),
+        ),
+        TransactionAdditionalInfo::DePoolReceiveAnswer(data) => (
+            "de_pool_receive_answer",
+            ObjectBuilder::new()
+                .set("errorCode", data.error_code)
+                .set("comment", data.comment.to_string())
+                .build(),
+        ),
+        TransactionAdditionalInfo::TokenWalletDeployed(data) => (
+            "token_wallet_deployed",
+            ObjectBuilder::new()
+                .set("rootTokenContract", data.root_token_contract.to_string())
+                .build(),
+        ),
+        TransactionAdditionalInfo::WalletInteraction(data) => {
+            ("wallet_interaction", make_wallet_interaction_info(data)?)
+        }
+    };
+
+    Ok(ObjectBuilder::new()
+        .set("type", kind)
+        .set("data", data)
+        .build())
+}
+
+fn make_wallet_interaction_info(
+    data: nt::core::models::WalletInteractionInfo,
+) -> Result<JsValue, JsValue> {
+    use nt::core::models::{KnownPayload, WalletInteractionMethod};
+
+    let known_payload = match data.known_payload {
+        Some(KnownPayload::Comment(comment)) => Some(("comment", JsValue::from(comment))),
+        Some(KnownPayload::TokenOutgoingTransfer(data)) => Some((
+            "token_outgoing_transfer",
+            crate::token_wallet::make_token_outgoing_transfer(data),
+        )),
+        Some(KnownPayload::TokenSwapBack(data)) => Some((
+            "token_swap_back",
+            crate::token_wallet::make_token_swap_back(data)?,
+        )),
+        None => None,
+    };
+
+    let method = match data.method {
+        WalletInteractionMethod::WalletV3Transfer => ObjectBuilder::new()
+            .set("type", "wallet_v3_transfer")
+            .build(),
+        WalletInteractionMethod::Multisig(transaction) => ObjectBuilder::new()
+            .set("type", "multisig")
+            .set("data", make_multisig_transaction(*transaction)?)
+            .build(),
+    };
+
+    Ok(ObjectBuilder::new()
+        .set(
+            "recipient",
+            data.recipient.map(|address| address.to_string()),
+        )
+        .set(
+            "knownPayload",
+            known_payload.map(|(kind, data)| {
+                ObjectBuilder::new()
+                    .set("type", kind)
+                    .set("data", data)
+                    .build()
+            }),
+        )
+        .set("method", method)
+        .build())
+}
+
+fn make_multisig_transaction(
+    data: nt::core::models::MultisigTransaction,
+) -> Result<JsValue, JsValue> {
+    use nt::core::models::MultisigTransaction;
+
+    let (kind, data) = match data {
+        MultisigTransaction::Send(data) => (
+            "send",
+            ObjectBuilder::new()
+                .set("dest", data.dest.to_string())
+                .set("value", data.value.to_string())
+                .set("bounce", data.bounce)
+                .set("flags", data.flags)
+                .set("payload", serialize_boc(&data.payload)?)
+                .build(),
+        ),
+        MultisigTransaction::Submit(data) => (
+            "submit",
+            ObjectBuilder::new()
+                .set("custodian", data.custodian.to_hex_string())
+                .set("dest", data.dest.to_string())
+                .set("value", data.value.to_string())
+                .set("bounce", data.bounce)
+                .set("allBalance", data.all_balance)
+                .set("payload", serialize_boc(&data.payload)?)
+                .set("transId", data.trans_id.to_string())
+                .build(),
+        ),
+        MultisigTransaction::Confirm(data) => (
+            "confirm",
+            ObjectBuilder::new()
+                .set("custodian", data.custodian.to_hex_string())
+                .set("transactionId", data.transaction_id.to_string())
+                .build(),
+        ),
+    };
+
+    Ok(ObjectBuilder::new()
+        .set("type", kind)
+        .set("data", data)
+        .build())
+}
+
+fn make_custodians<'a, I>(custodians: I) -> StringArray
+where
+    I: Iterator<Item = &'a ton_types::UInt256>,
+{
+    custodians
+        .map(|custodian| JsValue::from(custodian.to_hex_string()))
+        .collect::<js_sys::Array>()
+        .unchecked_into()
+}
+
+fn prepare_transfer_action(
+    clock: &dyn nt::utils::Clock,
+    wallet_type: nt::core::ton_wallet::WalletType,
+    account_stuff: &ton_block::AccountStuff,
+    public_key: &ed25519_dalek::PublicKey,
+    mut gifts: Vec<Gift>,
+    expiration: nt::core::models::Expiration,
+) -> Result<TransferAction, JsValue> {
+    use nt::core::ton_wallet::WalletType;
+
+    match wallet_type {
+        WalletType::WalletV3 => {
+            wallet_v3::prepare_transfer(clock, public_key, account_stuff, 0, gifts, expiration)
+        }
+        WalletType::HighloadWalletV2 => {
+            if gifts.len() > HIGHLOAD_WALLET_V2_MAX_GIFTS {
+                return Err(TonWalletError::TooManyGifts).handle_error();
+            }
+            highload_wallet_v2::prepare_transfer(
+                clock,
+                public_key,
+                account_stuff,
+                gifts,
+                expiration,
+            )
+        }
+        WalletType::EverWallet => ever_wallet::prepare_transfer(
+            clock,
+            public_key,
+            account_stuff,
+            account_stuff.addr.clone(),
+            gifts,
+            expiration,
+        ),
+        WalletType::Multisig(multisig_type) => {
+            let gift = match gifts.pop() {
+                Some(gift) if gifts.is_empty() => gift,
+                _ => return Err(TonWalletError::SingleGiftExpected).handle_error(),
+            };
+
+            // NOTE: transfers from wallets with several custodians must be submitted
+            // as pending transactions instead of being sent directly
+            let has_multiple_owners =
+                multisig::get_custodians(clock, multisig_type, Cow::Borrowed(account_stuff))
+                    .handle_error()?
+                    .len()
+                    > 1;
+
+            multisig::prepare_transfer(
+                clock,
+                multisig_type,
+                public_key,
+                has_multiple_owners,
+                account_stuff.addr.clone(),
+                gift,
+                expiration,
+            )
+        }
+    }
+    .handle_error()
+}
+
+pub fn make_transfer_message(action: TransferAction) -> Result<UnsignedMessage, JsValue> {
+    match action {
+        TransferAction::Sign(inner) => Ok(UnsignedMessage { inner }),
+        TransferAction::DeployFirst => Err(TonWalletError::DeployFirst).handle_error(),
+    }
+}
+
+pub fn parse_gifts(gifts: GiftList) -> Result<Vec<Gift>, JsValue> {
+    let gifts: JsValue = gifts.into();
+    if !js_sys::Array::is_array(&gifts) {
+        return Err(TokensJsonError::ArrayExpected).handle_error();
+    }
+
+    gifts
+        .unchecked_into::<js_sys::Array>()
+        .iter()
+        .map(parse_gift)
+        .collect()
+}
+
+fn parse_gift(gift: JsValue) -> Result<Gift, JsValue> {
+    if !gift.is_object() {
+        return Err(TokensJsonError::ObjectExpected).handle_error();
+    }
+
+    let get_field = |key: &str| -> Result<Option<JsValue>, JsValue> {
+        let value = js_sys::Reflect::get(&gift, &JsValue::from_str(key))?;
+        Ok(if value.is_null() || value.is_undefined() {
+            None
+        } else {
+            Some(value)
+        })
+    };
+
+    let destination = match get_field("destination")?.and_then(|value| value.as_string()) {
+        Some(destination) => parse_address(&destination)?,
+        None => {
+            return Err(TokensJsonError::ParameterNotFound("destination".to_owned())).handle_error()
+        }
+    };
+
+    let amount = match get_field("amount")?.and_then(|value| value.as_string()) {
+        Some(amount) => u64::from_str(amount.trim())
+            .map_err(|_| "Invalid amount")
+            .handle_error()?,
+        None => return Err(TokensJsonError::ParameterNotFound("amount".to_owned())).handle_error(),
+    };
+
+    let bounce = match get_field("bounce")? {
+        Some(bounce) => match bounce.as_bool() {
+            Some(bounce) => bounce,
+            None => return Err(TokensJsonError::BoolExpected).handle_error(),
+        },
+        None => false,
+    };
+
+    let flags = match get_field("flags")? {
+        Some(flags) => match flags.as_f64() {
+            Some(flags) if flags.fract() == 0.0 && (0.0..=u8::MAX as f64).contains(&flags) => {
+                flags as u8
+            }
+            _ => return Err(TokensJsonError::InvalidNumber("flags".to_owned())).handle_error(),
+        },
+        None => DEFAULT_GIFT_FLAGS,
+    };
+
+    let body = match get_field("body")? {
+        Some(body) => Some(parse_cell_slice(&body.unchecked_into::<Boc>())?),
+        None => None,
+    };
+
+    let state_init = match get_field("stateInit")? {
+        Some(state_init) => Some(parse_state_init(&state_init.unchecked_into::<Boc>())?),
+        None => None,
+    };
+
+    Ok(Gift {
+        flags,
+        bounce,
+        destination,
+        amount,
+        body,
+        state_init,
+    })
+}
+
+pub fn parse_wallet_type(
+    wallet_type: WalletType,
+) -> Result<nt::core::ton_wallet::WalletType, JsValue> {
+    use nt::core::ton_wallet::WalletType;
+
+    let wallet_type = match JsValue::from(wallet_type).as_string() {
+        Some(wallet_type) => wallet_type,
+        None => return Err(TonWalletError::InvalidWalletType).handle_error(),
+    };
+
+    Ok(match wallet_type.as_str() {
+        "WalletV3" => WalletType::WalletV3,
+        "HighloadWalletV2" => WalletType::HighloadWalletV2,
+        "EverWallet" => WalletType::EverWallet,
+        "SafeMultisigWallet" => WalletType::Multisig(MultisigType::SafeMultisigWallet),
+        "SafeMultisigWallet24h" => WalletType::Multisig(MultisigType::SafeMultisigWallet24h),
+        "SetcodeMultisigWallet" => WalletType::Multisig(MultisigType::SetcodeMultisigWallet),
+        "SetcodeMultisigWallet24h" => WalletType::Multisig(MultisigType::SetcodeMultisigWallet24h),
+        "BridgeMultisigWallet" => WalletType::Multisig(MultisigType::BridgeMultisigWallet),
+        "SurfWallet" => WalletType::Multisig(MultisigType::SurfWallet),
+        "Multisig2" => WalletType::Multisig(MultisigType::Multisig2),
+        "Multisig2_1" => WalletType::Multisig(MultisigType::Multisig2_1),
+        _ => return Err(TonWalletError::InvalidWalletType).handle_error(),
+    })
+}
+
+pub fn make_wallet_type(wallet_type: nt::core::ton_wallet::WalletType) -> WalletType {
+    use nt::core::ton_wallet::WalletType;
+
+    JsValue::from(match wallet_type {
+        WalletType::WalletV3 => "WalletV3",
+        WalletType::HighloadWalletV2 => "HighloadWalletV2",
+        WalletType::EverWallet => "EverWallet",
+        WalletType::Multisig(multisig_type) => match multisig_type {
+            MultisigType::SafeMultisigWallet => "SafeMultisigWallet",
+            MultisigType::SafeMultisigWallet24h => "SafeMultisigWallet24h",
+            MultisigType::SetcodeMultisigWallet => "SetcodeMultisigWallet",
+            MultisigType::SetcodeMultisigWallet24h => "SetcodeMultisigWallet24h",
+            MultisigType::BridgeMultisigWallet => "BridgeMultisigWallet",
+            MultisigType::SurfWallet => "SurfWallet",
+            MultisigType::Multisig2 => "Multisig2",
+            MultisigType::Multisig2_1 => "Multisig2_1",
+        },
+    })
+    .unchecked_into()
+}
+
+fn parse_multisig_type(wallet_type: WalletType) -> Result<MultisigType, JsValue> {
+    match parse_wallet_type(wallet_type)? {
+        nt::core::ton_wallet::WalletType::Multisig(multisig_type) => Ok(multisig_type),
+        _ => Err(TonWalletError::MultisigExpected).handle_error(),
+    }
+}
+
+struct ParsedMultisigDeployParams {
+    owners: Vec<ed25519_dalek::PublicKey>,
+    req_confirms: u8,
+    expiration_time: Option<u32>,
+}
+
+fn parse_multisig_deploy_params(
+    params: Option<MultisigDeployParams>,
+    public_key: &ed25519_dalek::PublicKey,
+) -> Result<ParsedMultisigDeployParams, JsValue> {
+    let params: JsValue = match params {
+        Some(params) => params.into(),
+        None => {
+            return Ok(ParsedMultisigDeployParams {
+                owners: vec![*public_key],
+                req_confirms: 1,
+                expiration_time: None,
+            })
+        }
+    };
+    if !params.is_object() {
+        return Err(TokensJsonError::ObjectExpected).handle_error();
+    }
+
+    let custodians = js_sys::Reflect::get(&params, &JsValue::from_str("custodians"))?;
+    if !js_sys::Array::is_array(&custodians) {
+        return Err(TokensJsonError::ArrayExpected).handle_error();
+    }
+    let owners = custodians
+        .unchecked_into::<js_sys::Array>()
+        .iter()
+        .map(|custodian| match custodian.as_string() {
+            Some(custodian) => parse_public_key(&custodian),
+            None => Err(TokensJsonError::StringExpected).handle_error(),
+        })
+        .collect::<Result<Vec<_>, JsValue>>()?;
+
+    let req_confirms = js_sys::Reflect::get(&params, &JsValue::from_str("reqConfirms"))?;
+    let req_confirms = match req_confirms.as_f64() {
+        Some(value) if value.fract() == 0.0 && (1.0..=owners.len() as f64).contains(&value) => {
+            value as u8
+        }
+        _ => return Err(TokensJsonError::InvalidNumber("reqConfirms".to_owned())).handle_error(),
+    };
+
+    let expiration_time = js_sys::Reflect::get(&params, &JsValue::from_str("expirationTime"))?;
+    let expiration_time = match expiration_time.as_f64() {
+        Some(value) if value.fract() == 0.0 && (0.0..=u32::MAX as f64).contains(&value) => {
+            Some(value as u32)
+        }
+        None if expiration_time.is_undefined() || expiration_time.is_null() => None,
+        _ => {
+            return Err(TokensJsonError::InvalidNumber("expirationTime".to_owned())).handle_error()
+        }
+    };
+
+    Ok(ParsedMultisigDeployParams {
+        owners,
+        req_confirms,
+        expiration_time,
+    })
+}
+
+/// Outbound actions limit of a single transaction
+const HIGHLOAD_WALLET_V2_MAX_GIFTS: usize = 255;
+
+/// Pay transfer fees separately and ignore action phase errors
+const DEFAULT_GIFT_FLAGS: u8 = 3;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TON_WALLET: &str = r#"
+export type WalletType =
+    | 'WalletV3'
+    | 'HighloadWalletV2'
+    | 'EverWallet'
+    | 'SafeMultisigWallet'
+    | 'SafeMultisigWallet24h'
+    | 'SetcodeMultisigWallet'
+    | 'SetcodeMultisigWallet24h'
+    | 'BridgeMultisigWallet'
+    | 'SurfWallet'
+    | 'Multisig2'
+    | 'Multisig2_1';
+
+export type MultisigDeployParams = {
+    custodians: string[],
+    reqConfirms: number,
+    expirationTime?: number,
+};
+
+export type TonWalletDetails = {
+    requiresSeparateDeploy: boolean,
+    minAmount: string,
+    maxMessages: number,
+    supportsPayload: boolean,
+    supportsStateInit: boolean,
+    supportsMultipleOwners: boolean,
+    supportsCodeUpdate: boolean,
+    expirationTime: number,
+    requiredConfirmations?: number,
+};
+
+/**
+ * Wallet info from `detectWalletType`. Wallet v4r2 is detected too,
+ * but it can't be used as a `WalletType` yet
+ */
+export type DetectedWallet = {
+    walletType: WalletType | 'WalletV4R2',
+    publicKey: string,
+    custodians?: string[],
+    details: TonWalletDetails,
+};
+
+export type TonWalletTransaction = Transaction & {
+    info?: TransactionAdditionalInfo,
+};
+
+export type TransactionAdditionalInfo =
+    | { type: 'comment', data: string }
+    | { type: 'de_pool_on_round_complete', data: DePoolOnRoundCompleteNotification }
+    | { type: 'de_pool_receive_answer', data: DePoolReceiveAnswerNotification }
+    | { type: 'token_wallet_deployed', data: TokenWalletDeployedNotification }
+    | { type: 'wallet_interaction', data: WalletInteractionInfo };
+
+export type DePoolOnRoundCompleteNotification = {
+    roundId: string,
+    reward: string,
+    ordinaryStake: string,
+    vestingStake: string,
+    lockStake: string,
+    reinvest: boolean,
+    reason: number,
+};
+
+export type DePoolReceiveAnswerNotification = {
+    errorCode: number,
+    comment: string,
+};
+
+export type TokenWalletDeployedNotification = {
+    rootTokenContract: string,
+};
+
+export type WalletInteractionInfo = {
+    recipient?: string,
+    knownPayload?: KnownPayload,
+    method: WalletInteractionMethod,
+};
+
+export type KnownPayload =
+    | { type: 'comment', data: string }
+    | { type: 'token_outgoing_transfer', data: TokenOutgoingTransfer }
+    | { type: 'token_swap_back', data: TokenSwapBack };
+
+export type WalletInteractionMethod =
+    | { type: 'wallet_v3_transfer' }
+    | { type: 'multisig', data: MultisigTransaction };
+
+export type MultisigTransaction =
+    | { type: 'send', data: MultisigSendTransaction }
+    | { type: 'submit', data: MultisigSubmitTransaction }
+    | { type: 'confirm', data: MultisigConfirmTransaction };
+
+export type MultisigSendTransaction = {
+    dest: string,
+    value: string,
+    bounce: boolean,
+    flags: number,
+    payload: string,
+};
+
+export type MultisigSubmitTransaction = {
+    custodian: string,
+    dest: string,
+    value: string,
+    bounce: boolean,
+    allBalance: boolean,
+    payload: string,
+    transId: string,
+};
+
+export type MultisigConfirmTransaction = {
+    custodian: string,
+    transactionId: string,
+};
+
+export type MultisigPendingTransaction = {
+    id: string,
+    confirmations: string[],
+    signsRequired: number,
+    signsReceived: number,
+    creator: string,
+    index: number,
+    dest: string,
+    value: string,
+    sendFlags: number,
+    payload: string,
+    bounce: boolean,
+};
+
+export type Gift = {
+    destination: string,
+    amount: string,
+    bounce?: boolean,
+    flags?: number,
+    body?: string | Uint8Array,
+    stateInit?: string | Uint8Array,
+};
+
+export type WalletV3State = {
+    seqno: number,
+    walletId: number,
+    publicKey: string,
+};
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Array<Gift>")]
+    pub type GiftList;
+
+    #[wasm_bindgen(typescript_type = "WalletV3State")]
+    pub type WalletV3State;
+
+    #[wasm_bindgen(typescript_type = "WalletType")]
+    pub type WalletType;
+
+    #[wasm_bindgen(typescript_type = "Promise<TonWallet>")]
+    pub type PromiseTonWallet;
+
+    #[wasm_bindgen(typescript_type = "Promise<UnsignedMessage>")]
+    pub type PromiseUnsignedMessage;
+
+    #[wasm_bindgen(typescript_type = "TonWalletDetails")]
+    pub type TonWalletDetails;
+
+    #[wasm_bindgen(typescript_type = "Array<TonWalletTransaction>")]
+    pub type TonWalletTransactionsList;
+
+    #[wasm_bindgen(typescript_type = "DetectedWallet")]
+    pub type DetectedWallet;
+
+    #[wasm_bindgen(typescript_type = "MultisigDeployParams")]
+    pub type MultisigDeployParams;
+
+    #[wasm_bindgen(typescript_type = "Array<MultisigPendingTransaction>")]
+    pub type MultisigPendingTransactionList;
+}
+
+#[derive(thiserror::Error, Debug)]
+enum TonWalletError {
+    #[error("Wallet must be deployed first")]
+    DeployFirst,
+    #[error("Too many gifts")]
+    TooManyGifts,
+    #[error("Invalid wallet type")]
+    InvalidWalletType,
+    #[error("Multisig wallet type expected")]
+    MultisigExpected,
+    #[error("Multisig transfer expects exactly one gift")]
+    SingleGiftExpected,
+}
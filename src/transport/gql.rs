@@ -60,6 +60,12 @@ impl GqlConnection {
             Ok(JsValue::from(next_block))
         })))
     }
+
+    /// Creates a transport for this connection, e.g. to be used in `Transport.fromConnections`
+    #[wasm_bindgen(js_name = "toTransport")]
+    pub fn to_transport(&self) -> super::Transport {
+        super::Transport::from_gql_connection(self)
+    }
 }
 
 impl GqlConnection {
@@ -22,4 +22,10 @@ impl JrpcConnection {
             clock: clock.clone_inner(),
         }
     }
+
+    /// Creates a transport for this connection, e.g. to be used in `Transport.fromConnections`
+    #[wasm_bindgen(js_name = "toTransport")]
+    pub fn to_transport(&self) -> super::Transport {
+        super::Transport::from_jrpc_connection(self)
+    }
 }
@@ -5,33 +5,127 @@ use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::*;
 
+use crate::executor::*;
 use crate::generic_contract::*;
 use crate::models::*;
+use crate::token_wallet::*;
+use crate::ton_wallet::*;
 use crate::utils::*;
 
 pub mod gql;
 pub mod jrpc;
+pub mod multi;
 
 #[derive(Clone)]
 pub enum TransportHandle {
     GraphQl(Arc<nt::transport::gql::GqlTransport>),
     Jrpc(Arc<nt::transport::jrpc::JrpcTransport>),
+    Multi(Arc<multi::MultiTransport>),
 }
 
 impl TransportHandle {
-    pub async fn get_block(&self, block_id: &str) -> Result<ton_block::Block, JsValue> {
+    /// Returns `None` for transports without access to blocks,
+    /// in which case subscriptions must be refreshed directly
+    pub async fn get_block(&self, block_id: &str) -> Result<Option<ton_block::Block>, JsValue> {
         match self {
-            Self::GraphQl(transport) => transport.get_block(block_id).await.handle_error(),
-            Self::Jrpc(_) => Err(TransportError::MethodNotSupported).handle_error(),
+            Self::GraphQl(transport) => {
+                transport.get_block(block_id).await.map(Some).handle_error()
+            }
+            Self::Jrpc(_) | Self::Multi(_) => Ok(None),
         }
     }
+
+    pub async fn get_latest_block(
+        &self,
+        address: &ton_block::MsgAddressInt,
+    ) -> Result<nt::transport::gql::LatestBlock, JsValue> {
+        match self {
+            Self::GraphQl(transport) => transport.get_latest_block(address).await.handle_error(),
+            Self::Jrpc(_) | Self::Multi(_) => {
+                let state = self
+                    .as_ref()
+                    .get_contract_state(address)
+                    .await
+                    .handle_error()?;
+                Ok(make_jrpc_latest_block(&state))
+            }
+        }
+    }
+
+    pub async fn wait_for_next_block(
+        &self,
+        clock: &dyn nt::utils::Clock,
+        current_block_id: &str,
+        address: &ton_block::MsgAddressInt,
+        timeout: std::time::Duration,
+    ) -> Result<String, JsValue> {
+        match self {
+            Self::GraphQl(transport) => transport
+                .wait_for_next_block(current_block_id, address, timeout)
+                .await
+                .handle_error(),
+            Self::Jrpc(_) | Self::Multi(_) => {
+                // NOTE: there are no blocks in JRPC, and blocks from different
+                // endpoints can't be mixed, so the account state is polled until
+                // it changes or the timeout is reached
+                let current_lt = parse_jrpc_block_lt(current_block_id);
+                let deadline = clock.now_ms_u64() + timeout.as_millis() as u64;
+                loop {
+                    let state = self
+                        .as_ref()
+                        .get_contract_state(address)
+                        .await
+                        .handle_error()?;
+                    let latest_block = make_jrpc_latest_block(&state);
+                    if Some(latest_block.end_lt) != current_lt || clock.now_ms_u64() >= deadline {
+                        return Ok(latest_block.id);
+                    }
+                    gloo_timers::future::sleep(JRPC_POLLING_INTERVAL).await;
+                }
+            }
+        }
+    }
+}
+
+/// Builds a pseudo block from the account state, so that block
+/// ids differ whenever the account changes
+fn make_jrpc_latest_block(
+    state: &nt::transport::models::RawContractState,
+) -> nt::transport::gql::LatestBlock {
+    let (end_lt, gen_utime) = match state {
+        nt::transport::models::RawContractState::Exists(contract) => (
+            contract.account.storage.last_trans_lt,
+            match contract.timings {
+                nt::abi::GenTimings::Known { gen_utime, .. } => gen_utime,
+                nt::abi::GenTimings::Unknown => 0,
+            },
+        ),
+        nt::transport::models::RawContractState::NotExists => (0, 0),
+    };
+
+    nt::transport::gql::LatestBlock {
+        id: format!("{JRPC_BLOCK_ID_PREFIX}{end_lt}:{gen_utime}"),
+        end_lt,
+        gen_utime,
+    }
+}
+
+fn parse_jrpc_block_lt(block_id: &str) -> Option<u64> {
+    let (lt, _) = block_id
+        .strip_prefix(JRPC_BLOCK_ID_PREFIX)?
+        .split_once(':')?;
+    u64::from_str(lt).ok()
 }
 
+const JRPC_BLOCK_ID_PREFIX: &str = "jrpc:";
+const JRPC_POLLING_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
 impl<'a> AsRef<dyn nt::transport::Transport + 'a> for TransportHandle {
     fn as_ref(&self) -> &(dyn nt::transport::Transport + 'a) {
         match self {
             Self::GraphQl(transport) => transport.as_ref(),
             Self::Jrpc(transport) => transport.as_ref(),
+            Self::Multi(transport) => transport.as_ref(),
         }
     }
 }
@@ -41,6 +135,7 @@ impl From<TransportHandle> for Arc<dyn nt::transport::Transport> {
         match handle {
             TransportHandle::GraphQl(transport) => transport,
             TransportHandle::Jrpc(transport) => transport,
+            TransportHandle::Multi(transport) => transport,
         }
     }
 }
@@ -73,6 +168,45 @@ impl Transport {
         }
     }
 
+    #[wasm_bindgen(js_name = "fromConnections")]
+    pub fn from_connections(
+        connections: multi::TransportConnectionList,
+        options: Option<multi::ConnectionsOptions>,
+        handler: Option<multi::TransportEndpointsHandlerImpl>,
+    ) -> Result<Transport, JsValue> {
+        let (strategy, health_check_interval) = multi::parse_connections_options(options)?;
+        let (transports, clock) = multi::parse_connections(connections)?;
+
+        let transport = Arc::new(multi::MultiTransport::new(
+            transports,
+            strategy,
+            clock.clone(),
+            handler.map(From::from),
+        ));
+        transport.spawn_health_checks(health_check_interval);
+
+        Ok(Self {
+            handle: TransportHandle::Multi(transport),
+            clock,
+        })
+    }
+
+    /// Returns the state of each endpoint for transports created with `fromConnections`
+    #[wasm_bindgen(js_name = "getEndpoints")]
+    pub fn get_endpoints(&self) -> Option<multi::EndpointInfoList> {
+        match &self.handle {
+            TransportHandle::Multi(transport) => Some(
+                transport
+                    .endpoints()
+                    .into_iter()
+                    .map(multi::make_endpoint_info)
+                    .collect::<js_sys::Array>()
+                    .unchecked_into(),
+            ),
+            _ => None,
+        }
+    }
+
     #[wasm_bindgen(js_name = "getSignatureId")]
     pub fn get_signature_id(&self) -> PromiseOptionSignatureId {
         let clock = self.clock.clone();
@@ -88,6 +222,41 @@ impl Transport {
         }))
     }
 
+    #[wasm_bindgen(js_name = "getLatestBlock")]
+    pub fn get_latest_block(&self, address: &str) -> Result<PromiseLatestBlock, JsValue> {
+        let address = parse_address(address)?;
+        let handle = self.handle.clone();
+
+        Ok(JsCast::unchecked_into(future_to_promise(async move {
+            let latest_block = handle.get_latest_block(&address).await?;
+            Ok(make_latest_block(latest_block))
+        })))
+    }
+
+    #[wasm_bindgen(js_name = "waitForNextBlock")]
+    pub fn wait_for_next_block(
+        &self,
+        current_block_id: String,
+        address: &str,
+        timeout: u32,
+    ) -> Result<PromiseString, JsValue> {
+        let address = parse_address(address)?;
+        let clock = self.clock.clone();
+        let handle = self.handle.clone();
+
+        Ok(JsCast::unchecked_into(future_to_promise(async move {
+            let next_block = handle
+                .wait_for_next_block(
+                    clock.as_ref(),
+                    &current_block_id,
+                    &address,
+                    std::time::Duration::from_secs(timeout as u64),
+                )
+                .await?;
+            Ok(JsValue::from(next_block))
+        })))
+    }
+
     #[wasm_bindgen(js_name = "subscribeToGenericContract")]
     pub fn subscribe_to_generic_contract_wallet(
         &self,
@@ -102,7 +271,7 @@ impl Transport {
 
         Ok(JsCast::unchecked_into(future_to_promise(async move {
             let contract = nt::core::generic_contract::GenericContract::subscribe(
-                clock,
+                clock.clone(),
                 handle.clone().into(),
                 address,
                 handler,
@@ -111,7 +280,68 @@ impl Transport {
             .await
             .handle_error()?;
 
-            Ok(JsValue::from(GenericContract::new(handle, contract)))
+            Ok(JsValue::from(GenericContract::new(clock, handle, contract)))
+        })))
+    }
+
+    #[wasm_bindgen(js_name = "subscribeToWallet")]
+    pub fn subscribe_to_wallet(
+        &self,
+        public_key: &str,
+        wallet_type: WalletType,
+        workchain: i8,
+        handler: TonWalletSubscriptionHandlerImpl,
+    ) -> Result<PromiseTonWallet, JsValue> {
+        let public_key = parse_public_key(public_key)?;
+        let wallet_type = parse_wallet_type(wallet_type)?;
+
+        let clock = self.clock.clone();
+        let handle = self.handle.clone();
+        let handler = Arc::new(TonWalletSubscriptionHandler::from(handler));
+
+        Ok(JsCast::unchecked_into(future_to_promise(async move {
+            let wallet = nt::core::ton_wallet::TonWallet::subscribe(
+                clock.clone(),
+                handle.clone().into(),
+                workchain,
+                public_key,
+                wallet_type,
+                handler,
+            )
+            .await
+            .handle_error()?;
+
+            Ok(JsValue::from(TonWallet::new(clock, handle, wallet)))
+        })))
+    }
+
+    #[wasm_bindgen(js_name = "subscribeToTokenWallet")]
+    pub fn subscribe_to_token_wallet(
+        &self,
+        owner: &str,
+        root_token_contract: &str,
+        handler: TokenWalletSubscriptionHandlerImpl,
+    ) -> Result<PromiseTokenWallet, JsValue> {
+        let owner = parse_address(owner)?;
+        let root_token_contract = parse_address(root_token_contract)?;
+
+        let clock = self.clock.clone();
+        let handle = self.handle.clone();
+        let handler = Arc::new(TokenWalletSubscriptionHandler::from(handler));
+
+        Ok(JsCast::unchecked_into(future_to_promise(async move {
+            let wallet = nt::core::token_wallet::TokenWallet::new(
+                clock,
+                handle.clone().into(),
+                owner,
+                root_token_contract,
+                handler,
+                false,
+            )
+            .await
+            .handle_error()?;
+
+            Ok(JsValue::from(TokenWallet::new(handle, wallet)))
         })))
     }
 
@@ -134,6 +364,35 @@ impl Transport {
         })))
     }
 
+    #[wasm_bindgen(js_name = "getTokenRootDetails")]
+    pub fn get_token_root_details(
+        &self,
+        root_token_contract: &str,
+    ) -> Result<PromiseRootTokenContractDetails, JsValue> {
+        let root_token_contract = parse_address(root_token_contract)?;
+        let clock = self.clock.clone();
+        let handle = self.handle.clone();
+
+        Ok(JsCast::unchecked_into(future_to_promise(async move {
+            let contract = match handle
+                .as_ref()
+                .get_contract_state(&root_token_contract)
+                .await
+                .handle_error()?
+            {
+                nt::transport::models::RawContractState::Exists(contract) => contract,
+                nt::transport::models::RawContractState::NotExists => {
+                    return Err(TransportError::AccountNotFound).handle_error()
+                }
+            };
+
+            let details = nt::core::token_wallet::RootTokenContractState(&contract)
+                .guess_details(clock.as_ref())
+                .handle_error()?;
+            Ok(make_root_token_contract_details(details).unchecked_into())
+        })))
+    }
+
     #[wasm_bindgen(js_name = "getAccountsByCodeHash")]
     pub fn get_accounts_by_code_hash(
         &self,
@@ -236,10 +495,49 @@ impl Transport {
             )
         })))
     }
+
+    #[wasm_bindgen(js_name = "emulateMessageTree")]
+    pub fn emulate_message_tree(
+        &self,
+        config: &Boc,
+        accounts: Option<AccountsMap>,
+        message: &Boc,
+        utime: u32,
+        options: Option<EmulateMessageTreeOptions>,
+    ) -> Result<PromiseEmulatedMessageTree, JsValue> {
+        let message = parse_message(message)?;
+        let emulator = MessageTreeEmulator::new(config, accounts, message, utime, options)?
+            .with_transport(self.handle.clone());
+        Ok(JsCast::unchecked_into(future_to_promise(emulator.run())))
+    }
+
+    #[wasm_bindgen(js_name = "estimateFees")]
+    pub fn estimate_fees(
+        &self,
+        config: &Boc,
+        accounts: Option<AccountsMap>,
+        message: &EstimatedMessage,
+        utime: u32,
+        options: Option<EstimateFeesOptions>,
+    ) -> Result<PromiseEstimatedFees, JsValue> {
+        let include_chain = parse_include_chain(options.as_ref())?;
+        let message = parse_estimated_message(message)?;
+        let emulator = MessageTreeEmulator::new(
+            config,
+            accounts,
+            message,
+            utime,
+            options.map(JsCast::unchecked_into),
+        )?
+        .with_transport(self.handle.clone());
+        Ok(JsCast::unchecked_into(future_to_promise(
+            emulator.estimate_fees(include_chain),
+        )))
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
 enum TransportError {
-    #[error("Method not supported")]
-    MethodNotSupported,
+    #[error("Account not found")]
+    AccountNotFound,
 }
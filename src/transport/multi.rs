@@ -0,0 +1,533 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+use anyhow::Result;
+use nt::utils::{Clock, TrustMe};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::utils::*;
+
+/// Routes requests over several endpoints, tracking their latency
+/// and errors to choose the next one according to the strategy
+pub struct MultiTransport {
+    endpoints: Vec<Endpoint>,
+    strategy: RoutingStrategy,
+    clock: Arc<nt::utils::ClockWithOffset>,
+    next: AtomicUsize,
+    active: Mutex<Option<usize>>,
+    handler: Option<TransportEndpointsHandler>,
+}
+
+impl MultiTransport {
+    pub fn new(
+        transports: Vec<Arc<dyn nt::transport::Transport>>,
+        strategy: RoutingStrategy,
+        clock: Arc<nt::utils::ClockWithOffset>,
+        handler: Option<TransportEndpointsHandler>,
+    ) -> Self {
+        Self {
+            endpoints: transports
+                .into_iter()
+                .map(|transport| Endpoint {
+                    transport,
+                    state: Default::default(),
+                })
+                .collect(),
+            strategy,
+            clock,
+            next: AtomicUsize::new(0),
+            active: Mutex::new(None),
+            handler,
+        }
+    }
+
+    /// Periodically checks all endpoints until the transport is dropped
+    pub fn spawn_health_checks(self: &Arc<Self>, interval: std::time::Duration) {
+        let weak = Arc::downgrade(self);
+        wasm_bindgen_futures::spawn_local(async move {
+            while let Some(transport) = Weak::upgrade(&weak) {
+                transport.check_endpoints().await;
+                drop(transport);
+                gloo_timers::future::sleep(interval).await;
+            }
+        });
+    }
+
+    pub async fn check_endpoints(&self) {
+        // NOTE: any address works here, the zero one is just the simplest
+        let address = ton_block::MsgAddressInt::default();
+        for endpoint in &self.endpoints {
+            // NOTE: capabilities and config are cached by transports,
+            // so a small uncached request is used to measure the round trip
+            let started_at = self.clock.now_ms_f64();
+            let result = endpoint.transport.get_contract_state(&address).await;
+            self.record_result(endpoint, started_at, result.is_ok());
+        }
+        self.update_active_endpoint();
+    }
+
+    pub fn endpoints(&self) -> Vec<EndpointState> {
+        self.endpoints
+            .iter()
+            .map(|endpoint| *endpoint.state.lock().trust_me())
+            .collect()
+    }
+
+    async fn call<T, F, R>(&self, f: F) -> Result<T>
+    where
+        F: Fn(Arc<dyn nt::transport::Transport>) -> R,
+        R: Future<Output = Result<T>>,
+    {
+        let mut last_error = None;
+        for index in self.ordered_endpoints(true) {
+            let endpoint = &self.endpoints[index];
+
+            let started_at = self.clock.now_ms_f64();
+            let result = f(endpoint.transport.clone()).await;
+            self.record_result(endpoint, started_at, result.is_ok());
+            self.update_active_endpoint();
+
+            match result {
+                Ok(result) => return Ok(result),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| MultiTransportError::NoEndpoints.into()))
+    }
+
+    fn record_result(&self, endpoint: &Endpoint, started_at: f64, success: bool) {
+        let mut state = endpoint.state.lock().trust_me();
+        if success {
+            let latency = self.clock.now_ms_f64() - started_at;
+            state.latency = Some(match state.latency {
+                Some(prev) => prev + (latency - prev) * LATENCY_SMOOTHING,
+                None => latency,
+            });
+            state.consecutive_errors = 0;
+        } else {
+            state.errors += 1;
+            state.consecutive_errors += 1;
+        }
+    }
+
+    /// Round robin advances to the next endpoint only if `rotate` is set
+    fn ordered_endpoints(&self, rotate: bool) -> Vec<usize> {
+        let rotation = (rotate && self.strategy == RoutingStrategy::RoundRobin)
+            .then(|| self.next.fetch_add(1, Ordering::Relaxed));
+        order_endpoints(&self.endpoints(), self.strategy, rotation)
+    }
+
+    fn update_active_endpoint(&self) {
+        // NOTE: round robin has no single active endpoint
+        if self.strategy == RoutingStrategy::RoundRobin {
+            return;
+        }
+
+        let active = self.ordered_endpoints(false).first().copied();
+        let changed = {
+            let mut current = self.active.lock().trust_me();
+            std::mem::replace(&mut *current, active) != active
+        };
+
+        if let (true, Some(handler), Some(active)) = (changed, &self.handler, active) {
+            handler.on_active_endpoint_changed(active);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl nt::transport::Transport for MultiTransport {
+    fn info(&self) -> nt::transport::TransportInfo {
+        let infos = self
+            .endpoints
+            .iter()
+            .map(|endpoint| endpoint.transport.info())
+            .collect::<Vec<_>>();
+
+        nt::transport::TransportInfo {
+            max_transactions_per_fetch: infos
+                .iter()
+                .map(|info| info.max_transactions_per_fetch)
+                .min()
+                .unwrap_or_default(),
+            // NOTE: blocks from different endpoints can't be mixed,
+            // so the state is always polled directly
+            reliable_behavior: nt::core::models::ReliableBehavior::IntensivePolling,
+            has_key_blocks: infos.iter().all(|info| info.has_key_blocks),
+        }
+    }
+
+    async fn send_message(&self, message: &ton_block::Message) -> Result<()> {
+        self.call(move |transport| async move { transport.send_message(message).await })
+            .await
+    }
+
+    async fn get_contract_state(
+        &self,
+        address: &ton_block::MsgAddressInt,
+    ) -> Result<nt::transport::models::RawContractState> {
+        self.call(move |transport| async move { transport.get_contract_state(address).await })
+            .await
+    }
+
+    async fn get_accounts_by_code_hash(
+        &self,
+        code_hash: &ton_types::UInt256,
+        limit: u8,
+        continuation: &Option<ton_block::MsgAddressInt>,
+    ) -> Result<Vec<ton_block::MsgAddressInt>> {
+        self.call(move |transport| async move {
+            transport
+                .get_accounts_by_code_hash(code_hash, limit, continuation)
+                .await
+        })
+        .await
+    }
+
+    async fn get_transactions(
+        &self,
+        address: &ton_block::MsgAddressInt,
+        from_lt: u64,
+        count: u8,
+    ) -> Result<Vec<nt::transport::models::RawTransaction>> {
+        self.call(move |transport| async move {
+            transport.get_transactions(address, from_lt, count).await
+        })
+        .await
+    }
+
+    async fn get_transaction(
+        &self,
+        id: &ton_types::UInt256,
+    ) -> Result<Option<nt::transport::models::RawTransaction>> {
+        self.call(move |transport| async move { transport.get_transaction(id).await })
+            .await
+    }
+
+    async fn get_dst_transaction(
+        &self,
+        message_hash: &ton_types::UInt256,
+    ) -> Result<Option<nt::transport::models::RawTransaction>> {
+        self.call(move |transport| async move { transport.get_dst_transaction(message_hash).await })
+            .await
+    }
+
+    async fn get_latest_key_block(&self) -> Result<ton_block::Block> {
+        self.call(|transport| async move { transport.get_latest_key_block().await })
+            .await
+    }
+
+    async fn get_capabilities(
+        &self,
+        clock: &dyn Clock,
+    ) -> Result<nt::core::models::NetworkCapabilities> {
+        self.call(move |transport| async move { transport.get_capabilities(clock).await })
+            .await
+    }
+
+    async fn get_blockchain_config(
+        &self,
+        clock: &dyn Clock,
+        force: bool,
+    ) -> Result<ton_executor::BlockchainConfig> {
+        self.call(
+            move |transport| async move { transport.get_blockchain_config(clock, force).await },
+        )
+        .await
+    }
+}
+
+/// Returns healthy endpoints in the order of the strategy,
+/// followed by failed endpoints as a last resort.
+///
+/// `rotation` is the round robin counter, endpoints are not rotated without it
+fn order_endpoints(
+    states: &[EndpointState],
+    strategy: RoutingStrategy,
+    rotation: Option<usize>,
+) -> Vec<usize> {
+    let (mut healthy, failed): (Vec<_>, Vec<_>) =
+        (0..states.len()).partition(|&index| states[index].is_healthy());
+
+    match strategy {
+        RoutingStrategy::Failover => {}
+        RoutingStrategy::RoundRobin => {
+            if let (Some(rotation), false) = (rotation, healthy.is_empty()) {
+                let offset = rotation % healthy.len();
+                healthy.rotate_left(offset);
+            }
+        }
+        RoutingStrategy::Fastest => healthy.sort_by(|&a, &b| {
+            let latency = |index: usize| states[index].latency.unwrap_or(f64::INFINITY);
+            latency(a).total_cmp(&latency(b))
+        }),
+    }
+
+    healthy.extend(failed);
+    healthy
+}
+
+struct Endpoint {
+    transport: Arc<dyn nt::transport::Transport>,
+    state: Mutex<EndpointState>,
+}
+
+#[derive(Default, Copy, Clone)]
+pub struct EndpointState {
+    /// Smoothed response time in milliseconds
+    pub latency: Option<f64>,
+    pub errors: u32,
+    pub consecutive_errors: u32,
+}
+
+impl EndpointState {
+    fn is_healthy(&self) -> bool {
+        self.consecutive_errors == 0
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum RoutingStrategy {
+    Failover,
+    RoundRobin,
+    Fastest,
+}
+
+pub fn parse_connections(
+    connections: TransportConnectionList,
+) -> Result<ParsedConnections, JsValue> {
+    let connections: JsValue = connections.into();
+    if !js_sys::Array::is_array(&connections) {
+        return Err(TokensJsonError::ArrayExpected).handle_error();
+    }
+
+    let transports = connections
+        .unchecked_into::<js_sys::Array>()
+        .iter()
+        .map(|connection| {
+            if !connection.is_object() {
+                return Err(TokensJsonError::ObjectExpected).handle_error();
+            }
+            connection
+                .unchecked_into::<TransportConnection>()
+                .to_transport()
+                .map_err(|_| TokensJsonError::ObjectExpected)
+                .handle_error()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // NOTE: health checks and latency are measured with a single clock
+    let clock = match transports.first() {
+        Some(transport) => transport.clock.clone(),
+        None => return Err(MultiTransportError::NoEndpoints).handle_error(),
+    };
+    if transports
+        .iter()
+        .any(|transport| !Arc::ptr_eq(&transport.clock, &clock))
+    {
+        return Err(MultiTransportError::ClockMismatch).handle_error();
+    }
+
+    let transports = transports
+        .into_iter()
+        .map(|transport| transport.handle.into())
+        .collect();
+    Ok((transports, clock))
+}
+
+type ParsedConnections = (
+    Vec<Arc<dyn nt::transport::Transport>>,
+    Arc<nt::utils::ClockWithOffset>,
+);
+
+pub fn parse_connections_options(
+    options: Option<ConnectionsOptions>,
+) -> Result<(RoutingStrategy, std::time::Duration), JsValue> {
+    let options: JsValue = match options {
+        Some(options) => options.into(),
+        None => JsValue::undefined(),
+    };
+    if options.is_undefined() || options.is_null() {
+        return Ok((RoutingStrategy::Failover, DEFAULT_HEALTH_CHECK_INTERVAL));
+    } else if !options.is_object() {
+        return Err(TokensJsonError::ObjectExpected).handle_error();
+    }
+
+    let strategy = match js_sys::Reflect::get(&options, &JsValue::from_str("strategy"))?
+        .as_string()
+        .as_deref()
+    {
+        None | Some("failover") => RoutingStrategy::Failover,
+        Some("roundRobin") => RoutingStrategy::RoundRobin,
+        Some("fastest") => RoutingStrategy::Fastest,
+        Some(_) => return Err(MultiTransportError::InvalidStrategy).handle_error(),
+    };
+
+    let health_check_interval =
+        match js_sys::Reflect::get(&options, &JsValue::from_str("healthCheckInterval"))?.as_f64() {
+            Some(interval) if interval > 0.0 => std::time::Duration::from_millis(interval as u64),
+            Some(_) => return Err(MultiTransportError::InvalidHealthCheckInterval).handle_error(),
+            None => DEFAULT_HEALTH_CHECK_INTERVAL,
+        };
+
+    Ok((strategy, health_check_interval))
+}
+
+pub fn make_endpoint_info(state: EndpointState) -> EndpointInfo {
+    ObjectBuilder::new()
+        .set("healthy", state.is_healthy())
+        .set("latency", state.latency)
+        .set("errors", state.errors)
+        .set("consecutiveErrors", state.consecutive_errors)
+        .build()
+        .unchecked_into()
+}
+
+const LATENCY_SMOOTHING: f64 = 0.2;
+const DEFAULT_HEALTH_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+unsafe impl Send for TransportEndpointsHandlerImpl {}
+unsafe impl Sync for TransportEndpointsHandlerImpl {}
+
+pub struct TransportEndpointsHandler {
+    inner: TransportEndpointsHandlerImpl,
+}
+
+impl From<TransportEndpointsHandlerImpl> for TransportEndpointsHandler {
+    fn from(inner: TransportEndpointsHandlerImpl) -> Self {
+        Self { inner }
+    }
+}
+
+impl TransportEndpointsHandler {
+    fn on_active_endpoint_changed(&self, endpoint: usize) {
+        self.inner.on_active_endpoint_changed(endpoint as u32);
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const CONNECTIONS_OPTIONS: &str = r#"
+export type TransportConnection = GqlConnection | JrpcConnection;
+
+export type RoutingStrategy = 'failover' | 'roundRobin' | 'fastest';
+
+export type ConnectionsOptions = {
+    strategy?: RoutingStrategy,
+    healthCheckInterval?: number,
+};
+
+export type EndpointInfo = {
+    healthy: boolean,
+    latency?: number,
+    errors: number,
+    consecutiveErrors: number,
+};
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "TransportConnection")]
+    pub type TransportConnection;
+
+    #[wasm_bindgen(method, catch, js_name = "toTransport")]
+    pub fn to_transport(this: &TransportConnection) -> Result<super::Transport, JsValue>;
+
+    #[wasm_bindgen(typescript_type = "Array<TransportConnection>")]
+    pub type TransportConnectionList;
+
+    #[wasm_bindgen(typescript_type = "ConnectionsOptions")]
+    pub type ConnectionsOptions;
+
+    #[wasm_bindgen(typescript_type = "EndpointInfo")]
+    pub type EndpointInfo;
+
+    #[wasm_bindgen(typescript_type = "Array<EndpointInfo>")]
+    pub type EndpointInfoList;
+
+    #[wasm_bindgen(js_name = "TransportEndpointsHandler")]
+    pub type TransportEndpointsHandlerImpl;
+
+    /// Called with the index of the endpoint which will be used first.
+    /// Not called for the round robin strategy
+    #[wasm_bindgen(method, js_name = "onActiveEndpointChanged")]
+    pub fn on_active_endpoint_changed(this: &TransportEndpointsHandlerImpl, endpoint: u32);
+}
+
+#[derive(thiserror::Error, Debug)]
+enum MultiTransportError {
+    #[error("No endpoints")]
+    NoEndpoints,
+    #[error("Invalid routing strategy")]
+    InvalidStrategy,
+    #[error("Invalid health check interval")]
+    InvalidHealthCheckInterval,
+    #[error("Connections must share the same clock")]
+    ClockMismatch,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(latency: Option<f64>, consecutive_errors: u32) -> EndpointState {
+        EndpointState {
+            latency,
+            errors: consecutive_errors,
+            consecutive_errors,
+        }
+    }
+
+    #[test]
+    fn failover_keeps_endpoints_order() {
+        let states = [state(Some(30.0), 0), state(None, 1), state(Some(10.0), 0)];
+        assert_eq!(
+            order_endpoints(&states, RoutingStrategy::Failover, None),
+            [0, 2, 1]
+        );
+    }
+
+    #[test]
+    fn round_robin_rotates_healthy_endpoints() {
+        let states = [
+            state(None, 0),
+            state(None, 2),
+            state(None, 0),
+            state(None, 0),
+        ];
+        let order = |rotation| order_endpoints(&states, RoutingStrategy::RoundRobin, rotation);
+        assert_eq!(order(None), [0, 2, 3, 1]);
+        assert_eq!(order(Some(0)), [0, 2, 3, 1]);
+        assert_eq!(order(Some(1)), [2, 3, 0, 1]);
+        assert_eq!(order(Some(2)), [3, 0, 2, 1]);
+        assert_eq!(order(Some(3)), [0, 2, 3, 1]);
+    }
+
+    #[test]
+    fn fastest_prefers_lowest_latency() {
+        let states = [
+            state(None, 0),
+            state(Some(50.0), 0),
+            state(Some(5.0), 1),
+            state(Some(20.0), 0),
+        ];
+        assert_eq!(
+            order_endpoints(&states, RoutingStrategy::Fastest, None),
+            [3, 1, 0, 2]
+        );
+    }
+
+    #[test]
+    fn failed_endpoints_are_still_tried() {
+        let states = [state(None, 1), state(None, 3)];
+        for strategy in [
+            RoutingStrategy::Failover,
+            RoutingStrategy::RoundRobin,
+            RoutingStrategy::Fastest,
+        ] {
+            assert_eq!(order_endpoints(&states, strategy, Some(1)), [0, 1]);
+        }
+    }
+}
@@ -0,0 +1,691 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use num_bigint::BigInt;
+
+use nt::utils::{Clock, TrustMe};
+use ton_block::{Deserializable, Serializable};
+use ton_vm::executor::gas::gas_state::Gas;
+use ton_vm::executor::{Engine, EngineTraceInfo, EngineTraceInfoType};
+use ton_vm::stack::{Stack, StackItem};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::utils::*;
+
+/// Collects TVM steps reported by the engine trace callback.
+///
+/// Only the topmost stack items are stored for each step (truncated
+/// if too long), and steps beyond the limit are dropped
+#[derive(Default, Clone)]
+pub struct TraceLog {
+    steps: Arc<Mutex<Vec<TraceStep>>>,
+}
+
+struct TraceStep {
+    info_type: &'static str,
+    step: u32,
+    cmd: String,
+    code_hash: ton_types::UInt256,
+    code_offset: usize,
+    gas_used: i64,
+    gas_cmd: i64,
+    stack: Vec<String>,
+    stack_depth: usize,
+    cell_load: Option<ton_types::UInt256>,
+}
+
+impl TraceLog {
+    pub fn make_callback(&self) -> impl Fn(&Engine, &EngineTraceInfo) + Send + Sync + 'static {
+        let steps = self.steps.clone();
+        move |_, info| {
+            let mut steps = steps.lock().trust_me();
+            if steps.len() >= MAX_TRACE_STEPS {
+                return;
+            }
+
+            let stack = &info.stack.storage;
+            let cell_load = match stack.last() {
+                Some(StackItem::Slice(slice)) if is_cell_load_cmd(&info.cmd_str) => {
+                    Some(slice.cell().repr_hash())
+                }
+                _ => None,
+            };
+
+            steps.push(TraceStep {
+                info_type: match info.info_type {
+                    EngineTraceInfoType::Start => "start",
+                    EngineTraceInfoType::Normal => "normal",
+                    EngineTraceInfoType::Finish => "finish",
+                    EngineTraceInfoType::Implicit => "implicit",
+                    EngineTraceInfoType::Exception => "exception",
+                    EngineTraceInfoType::Dump => "dump",
+                },
+                step: info.step,
+                cmd: info.cmd_str.clone(),
+                code_hash: info.cmd_code.cell().repr_hash(),
+                code_offset: info.cmd_code.pos(),
+                gas_used: info.gas_used,
+                gas_cmd: info.gas_cmd,
+                stack: stack
+                    .iter()
+                    .skip(stack.len().saturating_sub(MAX_TRACE_STACK_ITEMS))
+                    .map(|item| truncate_stack_item(item.to_string()))
+                    .collect(),
+                stack_depth: stack.len(),
+                cell_load,
+            });
+        }
+    }
+
+    pub fn to_js(&self) -> TvmTrace {
+        self.steps
+            .lock()
+            .trust_me()
+            .iter()
+            .map(|step| {
+                ObjectBuilder::new()
+                    .set("type", step.info_type)
+                    .set("step", step.step)
+                    .set("cmd", step.cmd.as_str())
+                    .set("codeHash", step.code_hash.to_hex_string())
+                    .set("codeOffset", step.code_offset as u32)
+                    .set("gasUsed", step.gas_used as f64)
+                    .set("gasCmd", step.gas_cmd as f64)
+                    .set(
+                        "stack",
+                        step.stack
+                            .iter()
+                            .map(|item| JsValue::from_str(item))
+                            .collect::<js_sys::Array>(),
+                    )
+                    .set("stackDepth", step.stack_depth as u32)
+                    .set(
+                        "cellLoad",
+                        step.cell_load.as_ref().map(|hash| hash.to_hex_string()),
+                    )
+                    .build()
+            })
+            .collect::<js_sys::Array>()
+            .unchecked_into()
+    }
+}
+
+fn is_cell_load_cmd(cmd: &str) -> bool {
+    let name = cmd.split_whitespace().next().unwrap_or_default();
+    CELL_LOAD_COMMANDS.contains(&name)
+}
+
+fn truncate_stack_item(mut item: String) -> String {
+    if let Some((index, _)) = item.char_indices().nth(MAX_TRACE_STACK_ITEM_LEN) {
+        item.truncate(index);
+        item.push_str("...");
+    }
+    item
+}
+
+/// Instructions which turn a cell into a slice, leaving it on top of the stack
+const CELL_LOAD_COMMANDS: [&str; 3] = ["CTOS", "XCTOS", "LDREFRTOS"];
+
+const MAX_TRACE_STEPS: usize = 100_000;
+const MAX_TRACE_STACK_ITEMS: usize = 16;
+const MAX_TRACE_STACK_ITEM_LEN: usize = 128;
+
+/// VM setup shared by all local runs
+pub struct VmParams {
+    capabilities: u64,
+    gas_limit: i64,
+    config_params: Option<ton_types::Cell>,
+}
+
+impl Default for VmParams {
+    fn default() -> Self {
+        Self {
+            capabilities: 0,
+            gas_limit: DEFAULT_GAS_LIMIT,
+            config_params: None,
+        }
+    }
+}
+
+impl VmParams {
+    /// Takes capabilities, gas limits and config params for c7 from the
+    /// blockchain config if it is specified
+    pub fn new(config: Option<&Boc>, workchain: i32) -> Result<Self, JsValue> {
+        let config = match config {
+            Some(config) => config,
+            None => return Ok(Self::default()),
+        };
+
+        let config =
+            ton_block::ConfigParams::construct_from_cell(parse_cell(config)?).handle_error()?;
+        let config = ton_executor::BlockchainConfig::with_config(config).handle_error()?;
+
+        let gas_config = config.get_gas_config(workchain == ton_block::MASTERCHAIN_ID);
+        Ok(Self {
+            capabilities: config.capabilities(),
+            gas_limit: gas_config.gas_limit.min(i64::MAX as u64) as i64,
+            config_params: config.raw_config().config_params.data().cloned(),
+        })
+    }
+}
+
+/// Without a config the gas limit is the same as the default basechain one
+const DEFAULT_GAS_LIMIT: i64 = 1_000_000;
+
+/// Encodes the ABI function call and runs it with the specified VM setup,
+/// optionally reporting every step into the trace log
+pub fn run_local(
+    clock: &dyn Clock,
+    account_stuff: &ton_block::AccountStuff,
+    function: &ton_abi::Function,
+    input: &[ton_abi::Token],
+    responsible: bool,
+    vm: &VmParams,
+    trace: Option<&TraceLog>,
+) -> Result<nt::abi::ExecutionOutput, JsValue> {
+    use nt::core::models::{Expiration, ExpireAt};
+
+    let dst = account_stuff.addr.clone();
+    let mut message = if responsible {
+        ton_block::Message::with_int_header(ton_block::InternalMessageHeader {
+            ihr_disabled: true,
+            bounce: false,
+            src: ton_block::MsgAddressIntOrNone::Some(dst.clone()),
+            dst,
+            ..Default::default()
+        })
+    } else {
+        ton_block::Message::with_ext_in_header(ton_block::ExternalInboundMessageHeader {
+            dst,
+            ..Default::default()
+        })
+    };
+
+    let body = if responsible {
+        function.encode_internal_input(input).handle_error()?
+    } else {
+        let time = clock.now_ms_u64();
+        let expire_at = ExpireAt::new_from_millis(Expiration::Timeout(60), time);
+
+        let mut header = HashMap::with_capacity(3);
+        header.insert("time".to_string(), ton_abi::TokenValue::Time(time));
+        header.insert(
+            "expire".to_string(),
+            ton_abi::TokenValue::Expire(expire_at.timestamp),
+        );
+        header.insert("pubkey".to_string(), ton_abi::TokenValue::PublicKey(None));
+
+        function
+            .encode_input(
+                &header,
+                input,
+                false,
+                None,
+                Some(account_stuff.addr.clone()),
+            )
+            .handle_error()?
+    };
+    message.set_body(body.into_cell().handle_error()?.into());
+
+    let utime = clock.now_sec_u64() as u32;
+    let lt = account_stuff.storage.last_trans_lt + 1;
+    let (result_code, messages) = call_msg(utime, lt, account_stuff, &message, vm, trace)?;
+
+    let tokens = messages.into_iter().find_map(|message| {
+        let is_output = match message.header() {
+            ton_block::CommonMsgInfo::IntMsgInfo(_) => responsible,
+            ton_block::CommonMsgInfo::ExtOutMsgInfo(_) => !responsible,
+            ton_block::CommonMsgInfo::ExtInMsgInfo(_) => false,
+        };
+        let body = message.body().filter(|_| is_output)?;
+        if !function
+            .is_my_output_message(body.clone(), responsible)
+            .unwrap_or_default()
+        {
+            return None;
+        }
+        function.decode_output(body, responsible).ok()
+    });
+
+    Ok(nt::abi::ExecutionOutput {
+        result_code,
+        tokens,
+    })
+}
+
+/// Executes the contract code with the message on stack and returns
+/// the exit code and all outgoing messages
+pub fn call_msg(
+    utime: u32,
+    lt: u64,
+    account_stuff: &ton_block::AccountStuff,
+    message: &ton_block::Message,
+    vm: &VmParams,
+    trace: Option<&TraceLog>,
+) -> Result<(i32, Vec<ton_block::Message>), JsValue> {
+    let message_cell = message.serialize().handle_error()?;
+    let selector = match message.header() {
+        ton_block::CommonMsgInfo::IntMsgInfo(_) => 0,
+        _ => -1,
+    };
+
+    let mut stack = Stack::new();
+    stack
+        .push(ton_vm::int!(account_stuff.storage.balance.grams.0))
+        .push(ton_vm::int!(0))
+        .push(StackItem::Cell(message_cell))
+        .push(StackItem::Slice(message.body().unwrap_or_default()))
+        .push(ton_vm::int!(selector));
+
+    let (exit_code, engine) = call(utime, lt, account_stuff, stack, vm, trace)?;
+    if exit_code != 0 && exit_code != 1 {
+        return Ok((exit_code, Vec::new()));
+    }
+
+    let actions = match engine.get_actions().as_cell() {
+        Ok(cell) => ton_block::OutActions::construct_from_cell(cell.clone()).handle_error()?,
+        Err(_) => Default::default(),
+    };
+
+    let messages = actions
+        .into_iter()
+        .filter_map(|action| match action {
+            ton_block::OutAction::SendMsg { out_msg, .. } => Some(out_msg),
+            _ => None,
+        })
+        .collect();
+
+    Ok((exit_code, messages))
+}
+
+/// Runs the contract code with the specified stack
+pub fn call(
+    utime: u32,
+    lt: u64,
+    account_stuff: &ton_block::AccountStuff,
+    stack: Stack,
+    vm: &VmParams,
+    trace: Option<&TraceLog>,
+) -> Result<(i32, Engine), JsValue> {
+    let state = match &account_stuff.storage.state {
+        ton_block::AccountState::AccountActive { state_init, .. } => state_init,
+        _ => return Err(nt::abi::ExtractionError::AccountIsNotActive).handle_error(),
+    };
+
+    let mut ctrls = ton_vm::stack::savelist::SaveList::new();
+    ctrls
+        .put(
+            4,
+            &mut StackItem::Cell(state.data.clone().unwrap_or_default()),
+        )
+        .handle_error()?;
+
+    let sci = build_contract_info(account_stuff, utime, lt, vm)?;
+    ctrls
+        .put(7, &mut sci.into_temp_data_item())
+        .handle_error()?;
+
+    let gas = Gas::new(vm.gas_limit, 0, vm.gas_limit, GAS_PRICE);
+    let mut engine = Engine::with_capabilities(vm.capabilities).setup_with_libraries(
+        ton_types::SliceData::from(state.code.clone().unwrap_or_default()),
+        Some(ctrls),
+        Some(stack),
+        Some(gas),
+        Vec::new(),
+    );
+    if let Some(trace) = trace {
+        engine.set_trace(Engine::TRACE_ALL);
+        engine.set_trace_callback(trace.make_callback());
+    }
+
+    let exit_code = match engine.execute() {
+        Ok(exit_code) => exit_code,
+        Err(e) => ton_vm::error::tvm_exception_or_custom_code(&e),
+    };
+
+    Ok((exit_code, engine))
+}
+
+/// NOTE: gas is not paid for local runs, so only the limit is important
+const GAS_PRICE: i64 = 10;
+
+#[wasm_bindgen(js_name = "runGetter")]
+pub fn run_getter(
+    clock: &ClockWithOffset,
+    account_stuff_boc: &Boc,
+    method: GetterMethod,
+    stack: Option<TvmStack>,
+    trace: Option<bool>,
+    config: Option<Boc>,
+) -> Result<GetterOutput, JsValue> {
+    let account_stuff = parse_account_stuff(account_stuff_boc)?;
+    let vm = VmParams::new(config.as_ref(), account_stuff.addr.workchain_id())?;
+    let method_id = parse_getter_method(method)?;
+
+    let mut args = match stack {
+        Some(stack) => parse_tvm_stack(stack.into())?,
+        None => Vec::new(),
+    };
+    args.push(ton_vm::int!(method_id));
+
+    let mut stack = Stack::new();
+    for item in args {
+        stack.push(item);
+    }
+
+    let trace = trace.unwrap_or_default().then(TraceLog::default);
+
+    let utime = clock.inner.now_sec_u64() as u32;
+    let lt = account_stuff.storage.last_trans_lt + 1;
+    let (exit_code, engine) = call(utime, lt, &account_stuff, stack, &vm, trace.as_ref())?;
+
+    let stack = if exit_code == 0 || exit_code == 1 {
+        Some(
+            engine
+                .stack()
+                .storage
+                .iter()
+                .map(make_tvm_stack_item)
+                .collect::<Result<js_sys::Array, JsValue>>()?,
+        )
+    } else {
+        None
+    };
+
+    Ok(ObjectBuilder::new()
+        .set("code", exit_code)
+        .set("stack", stack)
+        .set("trace", trace.as_ref().map(TraceLog::to_js))
+        .build()
+        .unchecked_into())
+}
+
+fn parse_getter_method(method: GetterMethod) -> Result<u32, JsValue> {
+    if let Some(name) = method.as_string() {
+        Ok(compute_getter_method_id(name.trim()))
+    } else if let Some(id) = method.as_f64() {
+        if id.fract() != 0.0 || !(0.0..=u32::MAX as f64).contains(&id) {
+            return Err(TokensJsonError::InvalidNumber(id.to_string())).handle_error();
+        }
+        Ok(id as u32)
+    } else {
+        Err(TokensJsonError::NumberExpected).handle_error()
+    }
+}
+
+/// Same as in FunC: `(crc16(name) & 0xffff) | 0x10000`
+fn compute_getter_method_id(name: &str) -> u32 {
+    let mut crc = 0u16;
+    for byte in name.as_bytes() {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc as u32 | 0x10000
+}
+
+fn parse_tvm_stack(stack: JsValue) -> Result<Vec<StackItem>, JsValue> {
+    if !js_sys::Array::is_array(&stack) {
+        return Err(TokensJsonError::ArrayExpected).handle_error();
+    }
+    js_sys::Array::from(&stack)
+        .iter()
+        .map(parse_tvm_stack_item)
+        .collect()
+}
+
+fn parse_tvm_stack_item(item: JsValue) -> Result<StackItem, JsValue> {
+    if !item.is_object() {
+        return Err(TokensJsonError::ObjectExpected).handle_error();
+    }
+
+    let kind = match js_sys::Reflect::get(&item, &JsValue::from_str("type"))?.as_string() {
+        Some(kind) => kind,
+        None => return Err(TokensJsonError::StringExpected).handle_error(),
+    };
+    let value = js_sys::Reflect::get(&item, &JsValue::from_str("value"))?;
+
+    Ok(match kind.as_str() {
+        "null" => StackItem::None,
+        "nan" => StackItem::nan(),
+        "int" => {
+            let value = if let Some(value) = value.as_string() {
+                parse_tvm_int(&value)?
+            } else if let Some(value) = value.as_f64() {
+                parse_tvm_safe_int(value)?
+            } else {
+                return Err(TokensJsonError::NumberExpected).handle_error();
+            };
+            StackItem::integer(ton_vm::stack::integer::IntegerData::from(value).handle_error()?)
+        }
+        "cell" => StackItem::Cell(parse_cell(value.unchecked_ref::<Boc>())?),
+        "slice" => StackItem::Slice(parse_cell_slice(value.unchecked_ref::<Boc>())?),
+        "tuple" => StackItem::tuple(parse_tvm_stack(value)?),
+        _ => return Err(TvmError::UnsupportedStackItem(kind)).handle_error(),
+    })
+}
+
+fn parse_tvm_int(value: &str) -> Result<BigInt, JsValue> {
+    BigInt::from_str(value.trim())
+        .map_err(|_| TokensJsonError::InvalidNumber(value.to_owned()))
+        .handle_error()
+}
+
+/// Numbers are accepted only if they are exactly representable
+/// (same as `Number.isSafeInteger`), larger values must be passed as strings
+fn parse_tvm_safe_int(value: f64) -> Result<BigInt, JsValue> {
+    if value.fract() != 0.0 {
+        return Err(TokensJsonError::IntegerValueExpected(value)).handle_error();
+    }
+    if !is_safe_integer(value) {
+        return Err(TvmError::UnsafeInteger(value)).handle_error();
+    }
+    Ok(BigInt::from(value as i64))
+}
+
+fn is_safe_integer(value: f64) -> bool {
+    value.fract() == 0.0 && value.abs() <= MAX_SAFE_INTEGER
+}
+
+/// `Number.MAX_SAFE_INTEGER`
+const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+fn make_tvm_stack_item(item: &StackItem) -> Result<JsValue, JsValue> {
+    let (kind, value) = match item {
+        StackItem::None => ("null", None),
+        StackItem::Integer(value) if value.is_nan() => ("nan", None),
+        StackItem::Integer(value) => ("int", Some(JsValue::from(value.to_string()))),
+        StackItem::Cell(cell) => ("cell", Some(JsValue::from(serialize_boc(cell)?))),
+        StackItem::Slice(slice) => (
+            "slice",
+            Some(JsValue::from(serialize_boc(&slice.clone().into_cell())?)),
+        ),
+        StackItem::Builder(builder) => {
+            let cell = builder.as_ref().clone().into_cell().handle_error()?;
+            ("builder", Some(JsValue::from(serialize_boc(&cell)?)))
+        }
+        StackItem::Tuple(items) => (
+            "tuple",
+            Some(JsValue::from(
+                items
+                    .iter()
+                    .map(make_tvm_stack_item)
+                    .collect::<Result<js_sys::Array, JsValue>>()?,
+            )),
+        ),
+        StackItem::Continuation(_) => ("continuation", None),
+    };
+
+    Ok(ObjectBuilder::new()
+        .set("type", kind)
+        .set("value", value)
+        .build())
+}
+
+fn build_contract_info(
+    account_stuff: &ton_block::AccountStuff,
+    utime: u32,
+    lt: u64,
+    vm: &VmParams,
+) -> Result<ton_vm::SmartContractInfo, JsValue> {
+    let address = account_stuff.addr.serialize().handle_error()?;
+
+    let mut info = ton_vm::SmartContractInfo::with_myself(address.into());
+    *info.block_lt_mut() = lt;
+    *info.trans_lt_mut() = lt;
+    *info.unix_time_mut() = utime;
+    *info.balance_remaining_grams_mut() = account_stuff.storage.balance.grams.0;
+    *info.balance_remaining_other_mut() = account_stuff.storage.balance.other_as_hashmap();
+    info.set_config_params(vm.config_params.clone());
+
+    Ok(info)
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const TVM_TRACE: &str = r#"
+export type TvmTraceStep = {
+    type: 'start' | 'normal' | 'finish' | 'implicit' | 'exception' | 'dump',
+    step: number,
+    cmd: string,
+    codeHash: string,
+    codeOffset: number,
+    gasUsed: number,
+    gasCmd: number,
+    stack: string[],
+    stackDepth: number,
+    cellLoad?: string,
+};
+
+export type TvmTrace = Array<TvmTraceStep>;
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TVM_STACK: &str = r#"
+/**
+ * Integers passed as numbers must be safe integers (`Number.isSafeInteger`),
+ * use strings for larger values
+ */
+export type TvmStackItem =
+    | { type: 'null' }
+    | { type: 'nan' }
+    | { type: 'int', value: string | number }
+    | { type: 'cell', value: string | Uint8Array }
+    | { type: 'slice', value: string | Uint8Array }
+    | { type: 'tuple', value: TvmStackItem[] };
+
+export type TvmStackOutputItem =
+    | { type: 'null' }
+    | { type: 'nan' }
+    | { type: 'int', value: string }
+    | { type: 'cell', value: string }
+    | { type: 'slice', value: string }
+    | { type: 'builder', value: string }
+    | { type: 'tuple', value: TvmStackOutputItem[] }
+    | { type: 'continuation' };
+
+export type GetterOutput = {
+    code: number,
+    stack?: TvmStackOutputItem[],
+    trace?: TvmTrace,
+};
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "TvmTrace")]
+    pub type TvmTrace;
+
+    #[wasm_bindgen(typescript_type = "string | number")]
+    pub type GetterMethod;
+
+    #[wasm_bindgen(typescript_type = "TvmStackItem[]")]
+    pub type TvmStack;
+
+    #[wasm_bindgen(typescript_type = "GetterOutput")]
+    pub type GetterOutput;
+}
+
+#[derive(thiserror::Error, Debug)]
+enum TvmError {
+    #[error("Unsupported stack item: {0}")]
+    UnsupportedStackItem(String),
+    #[error("Integer {0} is not a safe integer, pass it as a string")]
+    UnsafeInteger(f64),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_stack_items_are_truncated() {
+        let item = "x".repeat(MAX_TRACE_STACK_ITEM_LEN + 10);
+        let truncated = truncate_stack_item(item);
+        assert_eq!(truncated.len(), MAX_TRACE_STACK_ITEM_LEN + 3);
+        assert!(truncated.ends_with("..."));
+
+        assert_eq!(truncate_stack_item("123".to_owned()), "123");
+    }
+
+    #[test]
+    fn cell_load_commands_are_detected() {
+        assert!(is_cell_load_cmd("CTOS"));
+        assert!(is_cell_load_cmd("LDREFRTOS"));
+        assert!(!is_cell_load_cmd("PUSHINT 1"));
+        assert!(!is_cell_load_cmd(""));
+    }
+
+    #[test]
+    fn getter_method_id_is_computed_as_in_func() {
+        assert_eq!(compute_getter_method_id("seqno"), 85143);
+        assert_eq!(compute_getter_method_id(""), 0x10000);
+    }
+
+    #[test]
+    fn only_safe_integers_are_accepted_as_numbers() {
+        assert!(is_safe_integer(0.0));
+        assert!(is_safe_integer(-123.0));
+        assert!(is_safe_integer(MAX_SAFE_INTEGER));
+        assert!(is_safe_integer(-MAX_SAFE_INTEGER));
+        assert!(!is_safe_integer(MAX_SAFE_INTEGER + 1.0));
+        assert!(!is_safe_integer(-MAX_SAFE_INTEGER - 1.0));
+        assert!(!is_safe_integer(1e20));
+        assert!(!is_safe_integer(0.5));
+        assert!(!is_safe_integer(f64::NAN));
+        assert!(!is_safe_integer(f64::INFINITY));
+    }
+
+    #[test]
+    fn stack_values_round_trip() {
+        let values = [
+            BigInt::from(0),
+            BigInt::from(-1),
+            BigInt::from(i64::MIN),
+            BigInt::from(u128::MAX) * BigInt::from(u64::MAX),
+        ];
+        for value in values {
+            let item = StackItem::integer(
+                ton_vm::stack::integer::IntegerData::from(value.clone()).unwrap(),
+            );
+            let encoded = match &item {
+                StackItem::Integer(value) => value.to_string(),
+                _ => unreachable!(),
+            };
+            assert_eq!(BigInt::from_str(&encoded).unwrap(), value);
+        }
+
+        let mut builder = ton_types::BuilderData::new();
+        builder.append_u32(0xdeadbeef).unwrap();
+        let cell = builder.into_cell().unwrap();
+
+        let encoded = serialize_boc(&cell).unwrap();
+        assert_eq!(parse_cell(encoded.as_str()).unwrap(), cell);
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt::Write;
+
+pub fn generate_abi_typings(contract: &ton_abi::Contract) -> String {
+    let mut result = String::new();
+
+    let mut functions = contract.functions.values().collect::<Vec<_>>();
+    functions.sort_by(|a, b| a.name.cmp(&b.name));
+
+    result.push_str("export type Functions = {\n");
+    for function in functions {
+        let _ = writeln!(
+            result,
+            "    {}: {{\n        input: {},\n        output: {},\n    }},",
+            make_key(&function.name),
+            make_object_type(&function.inputs, TypeMode::Input),
+            make_object_type(&function.outputs, TypeMode::Output),
+        );
+    }
+    result.push_str("};\n\n");
+
+    let mut events = contract.events.values().collect::<Vec<_>>();
+    events.sort_by(|a, b| a.name.cmp(&b.name));
+
+    result.push_str("export type Events = {\n");
+    for event in events {
+        let _ = writeln!(
+            result,
+            "    {}: {},",
+            make_key(&event.name),
+            make_object_type(&event.inputs, TypeMode::Output),
+        );
+    }
+    result.push_str("};\n\n");
+
+    let mut data = contract.data.values().collect::<Vec<_>>();
+    data.sort_by_key(|item| item.key);
+    let data = data
+        .into_iter()
+        .map(|item| item.value.clone())
+        .collect::<Vec<_>>();
+
+    let _ = writeln!(
+        result,
+        "export type InitData = {};\n",
+        make_object_type(&data, TypeMode::Input)
+    );
+    let _ = writeln!(
+        result,
+        "export type Fields = {};",
+        make_object_type(&contract.fields, TypeMode::Output)
+    );
+
+    result
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum TypeMode {
+    Input,
+    Output,
+}
+
+fn make_object_type(params: &[ton_abi::Param], mode: TypeMode) -> String {
+    if params.is_empty() {
+        return "{}".to_owned();
+    }
+
+    let fields = params
+        .iter()
+        .map(|param| {
+            format!(
+                "{}: {}",
+                make_key(&param.name),
+                make_type(&param.kind, mode)
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("{{ {} }}", fields.join(", "))
+}
+
+// NOTE: input types must match `parse_token_value`, output types must match `make_token_value`
+fn make_type(kind: &ton_abi::ParamType, mode: TypeMode) -> String {
+    match kind {
+        ton_abi::ParamType::Uint(_)
+        | ton_abi::ParamType::Int(_)
+        | ton_abi::ParamType::VarUint(_)
+        | ton_abi::ParamType::VarInt(_)
+        | ton_abi::ParamType::Token
+        | ton_abi::ParamType::Time => match mode {
+            TypeMode::Input => "string | number".to_owned(),
+            TypeMode::Output => "string".to_owned(),
+        },
+        ton_abi::ParamType::Expire => match mode {
+            TypeMode::Input => "number | string".to_owned(),
+            TypeMode::Output => "number".to_owned(),
+        },
+        ton_abi::ParamType::Bool => "boolean".to_owned(),
+        ton_abi::ParamType::Tuple(params) => make_object_type(params, mode),
+        ton_abi::ParamType::Array(kind) | ton_abi::ParamType::FixedArray(kind, _) => {
+            format!("Array<{}>", make_type(kind, mode))
+        }
+        ton_abi::ParamType::Cell => match mode {
+            TypeMode::Input => "string | null".to_owned(),
+            TypeMode::Output => "string".to_owned(),
+        },
+        ton_abi::ParamType::Map(key, value) => {
+            let key = match mode {
+                TypeMode::Input => make_type(key, mode),
+                // Map keys are always stringified
+                TypeMode::Output => "string".to_owned(),
+            };
+            format!("Array<readonly [{}, {}]>", key, make_type(value, mode))
+        }
+        ton_abi::ParamType::Address
+        | ton_abi::ParamType::Bytes
+        | ton_abi::ParamType::FixedBytes(_)
+        | ton_abi::ParamType::String => "string".to_owned(),
+        ton_abi::ParamType::PublicKey => match mode {
+            TypeMode::Input => "string".to_owned(),
+            TypeMode::Output => "string | undefined".to_owned(),
+        },
+        ton_abi::ParamType::Optional(kind) => format!("{} | null", make_type(kind, mode)),
+        ton_abi::ParamType::Ref(kind) => make_type(kind, mode),
+    }
+}
+
+fn make_key(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_identifier = match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+        }
+        _ => false,
+    };
+
+    if is_identifier {
+        name.to_owned()
+    } else {
+        format!("{name:?}")
+    }
+}
@@ -136,21 +136,63 @@ pub fn parse_address(address: &str) -> Result<MsgAddressInt, JsValue> {
     MsgAddressInt::from_str(address.trim()).handle_error()
 }
 
-pub fn parse_state_init(state_init: &str) -> Result<ton_block::StateInit, JsValue> {
-    ton_block::StateInit::construct_from_base64(state_init).handle_error()
+pub fn parse_state_init<T: BocInput + ?Sized>(
+    state_init: &T,
+) -> Result<ton_block::StateInit, JsValue> {
+    ton_block::StateInit::construct_from_cell(parse_cell(state_init)?).handle_error()
 }
 
-pub fn parse_cell_slice(boc: &str) -> Result<ton_types::SliceData, JsValue> {
+pub fn parse_cell_slice<T: BocInput + ?Sized>(boc: &T) -> Result<ton_types::SliceData, JsValue> {
     parse_cell(boc).map(From::from)
 }
 
-pub fn parse_cell(boc: &str) -> Result<ton_types::Cell, JsValue> {
-    let boc = boc.trim();
-    if boc.is_empty() {
+pub fn parse_cell<T: BocInput + ?Sized>(boc: &T) -> Result<ton_types::Cell, JsValue> {
+    let bytes = boc.to_boc_bytes()?;
+    if bytes.is_empty() {
         Ok(ton_types::Cell::default())
     } else {
-        let body = base64::decode(boc).handle_error()?;
-        ton_types::deserialize_tree_of_cells(&mut body.as_slice()).handle_error()
+        ton_types::deserialize_tree_of_cells(&mut bytes.as_slice()).handle_error()
+    }
+}
+
+pub fn serialize_boc(cell: &ton_types::Cell) -> Result<String, JsValue> {
+    serialize_boc_bytes(cell).map(base64::encode)
+}
+
+pub fn serialize_boc_bytes(cell: &ton_types::Cell) -> Result<Vec<u8>, JsValue> {
+    ton_types::serialize_toc(cell).handle_error()
+}
+
+pub trait BocInput {
+    fn to_boc_bytes(&self) -> Result<Vec<u8>, JsValue>;
+}
+
+impl BocInput for str {
+    fn to_boc_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        let boc = self.trim();
+        if boc.is_empty() {
+            Ok(Vec::new())
+        } else {
+            base64::decode(boc).handle_error()
+        }
+    }
+}
+
+impl BocInput for String {
+    fn to_boc_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        self.as_str().to_boc_bytes()
+    }
+}
+
+impl BocInput for Boc {
+    fn to_boc_bytes(&self) -> Result<Vec<u8>, JsValue> {
+        if let Some(boc) = self.as_string() {
+            boc.to_boc_bytes()
+        } else if let Some(bytes) = self.dyn_ref::<js_sys::Uint8Array>() {
+            Ok(bytes.to_vec())
+        } else {
+            Err(TokensJsonError::BocExpected).handle_error()
+        }
     }
 }
 
@@ -188,10 +230,12 @@ pub fn parse_hex_bytes(data: &str) -> Result<Vec<u8>, hex::FromHexError> {
     hex::decode(data.strip_prefix("0x").unwrap_or(data))
 }
 
-pub fn parse_account_stuff(boc: &str) -> Result<ton_block::AccountStuff, JsValue> {
+pub fn parse_account_stuff<T: BocInput + ?Sized>(
+    boc: &T,
+) -> Result<ton_block::AccountStuff, JsValue> {
     use ton_block::MaybeDeserialize;
 
-    let bytes = base64::decode(boc).handle_error()?;
+    let bytes = boc.to_boc_bytes()?;
     ton_types::deserialize_tree_of_cells(&mut bytes.as_slice())
         .and_then(|cell| {
             let slice = &mut cell.into();
@@ -213,6 +257,19 @@ pub fn parse_account_stuff(boc: &str) -> Result<ton_block::AccountStuff, JsValue
         .handle_error()
 }
 
+pub fn make_existing_contract(
+    account: ton_block::AccountStuff,
+) -> nt::transport::models::ExistingContract {
+    let last_transaction_id = nt::abi::LastTransactionId::Inexact {
+        latest_lt: account.storage.last_trans_lt,
+    };
+    nt::transport::models::ExistingContract {
+        account,
+        timings: nt::abi::GenTimings::Unknown,
+        last_transaction_id,
+    }
+}
+
 pub fn parse_contract_abi(contract_abi: &str) -> Result<ton_abi::Contract, JsValue> {
     ton_abi::Contract::load(contract_abi).handle_error()
 }
@@ -238,6 +295,9 @@ extern "C" {
 
     #[wasm_bindgen(typescript_type = "Array<string>")]
     pub type StringArray;
+
+    #[wasm_bindgen(typescript_type = "string | Uint8Array")]
+    pub type Boc;
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -270,6 +330,8 @@ pub enum TokensJsonError {
     InvalidCell,
     #[error("Expected string")]
     StringExpected,
+    #[error("Expected BOC as base64 string or Uint8Array")]
+    BocExpected,
     #[error("Expected map item as array of key and value")]
     MapItemExpected,
     #[error("Invalid mapping key")]
@@ -287,3 +349,33 @@ pub enum TokensJsonError {
     #[error("Invalid components")]
     InvalidComponents,
 }
+
+impl TokensJsonError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ParameterCountMismatch => "ParameterCountMismatch",
+            Self::ObjectExpected => "ObjectExpected",
+            Self::MessageExpected => "MessageExpected",
+            Self::MessageBodyExpected => "MessageBodyExpected",
+            Self::ArrayExpected => "ArrayExpected",
+            Self::ParameterNotFound(_) => "ParameterNotFound",
+            Self::InvalidNumber(_) => "InvalidNumber",
+            Self::IntegerValueExpected(_) => "IntegerValueExpected",
+            Self::UnsignedValueExpected(_) => "UnsignedValueExpected",
+            Self::NumberExpected => "NumberExpected",
+            Self::BoolExpected => "BoolExpected",
+            Self::InvalidArrayLength(_) => "InvalidArrayLength",
+            Self::InvalidCell => "InvalidCell",
+            Self::StringExpected => "StringExpected",
+            Self::BocExpected => "BocExpected",
+            Self::MapItemExpected => "MapItemExpected",
+            Self::InvalidMappingKey => "InvalidMappingKey",
+            Self::InvalidAddress => "InvalidAddress",
+            Self::InvalidBytes => "InvalidBytes",
+            Self::InvalidBytesLength(_) => "InvalidBytesLength",
+            Self::InvalidPublicKey => "InvalidPublicKey",
+            Self::ParamTypeExpected => "ParamTypeExpected",
+            Self::InvalidComponents => "InvalidComponents",
+        }
+    }
+}